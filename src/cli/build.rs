@@ -18,7 +18,11 @@ use tokio::{
 };
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 
-use crate::standalone::metadata::{Metadata, CURRENT_EXE};
+use crate::standalone::{
+    manifest::Manifest,
+    metadata::{Metadata, CURRENT_EXE},
+    tracer::trace_requires,
+};
 
 const TARGET_BASE_DIR: Lazy<PathBuf> = Lazy::new(|| {
     BaseDirs::new()
@@ -44,6 +48,11 @@ pub struct BuildCommand {
     /// The target to compile for - defaults to the host triple
     #[clap(short, long)]
     pub target: Option<String>,
+
+    /// Also write a JSON manifest describing the
+    /// binary, next to the output file
+    #[clap(long)]
+    pub manifest: bool,
 }
 
 impl BuildCommand {
@@ -59,6 +68,11 @@ impl BuildCommand {
             .await
             .context("failed to read input file")?;
 
+        let target = self
+            .target
+            .clone()
+            .unwrap_or_else(|| format!("{}-{}", consts::OS, consts::ARCH));
+
         // Dynamically derive the base executable path based on the CLI arguments provided
         let (base_exe_path, output_path) = get_base_exe_path(self.target, output_path).await?;
 
@@ -68,9 +82,30 @@ impl BuildCommand {
             style("Compile").green().bold(),
             style(input_path_displayed).underlined()
         );
-        let patched_bin = Metadata::create_env_patched_bin(base_exe_path, source_code.clone())
+        // Bundle any modules that the script requires, so that the binary does not need them on disk
+        let modules = trace_requires(&self.input, &source_code)
             .await
-            .context("failed to create patched binary")?;
+            .context("failed to trace requires")?;
+        for module_name in modules.keys() {
+            println!(
+                "  {} module {}",
+                style("Bundle").cyan().bold(),
+                style(module_name).underlined()
+            );
+        }
+
+        // NOTE: We only keep the file stem here, without the extension to remove it from
+        // stack traces, same as `lune run`, and without the directories on this machine
+        let script_name = self
+            .input
+            .file_stem()
+            .context("input file has no name")?
+            .to_string_lossy()
+            .to_string();
+        let patched_bin =
+            Metadata::create_env_patched_bin(base_exe_path, script_name, source_code, modules)
+                .await
+                .context("failed to create patched binary")?;
 
         // And finally write the patched binary to the output file
         println!(
@@ -78,7 +113,21 @@ impl BuildCommand {
             style("Write").blue().bold(),
             style(output_path.display()).underlined()
         );
-        write_executable_file_to(output_path, patched_bin).await?; // Read & execute for all, write for owner
+        write_executable_file_to(&output_path, &patched_bin).await?; // Read & execute for all, write for owner
+
+        // Describe the binary for build pipelines, if requested
+        if self.manifest {
+            let manifest_path = Manifest::path_for(&output_path);
+            println!(
+                "   {} build manifest to {}",
+                style("Write").blue().bold(),
+                style(manifest_path.display()).underlined()
+            );
+            Manifest::from_patched_bin(target, &patched_bin)?
+                .write_to(manifest_path)
+                .await
+                .context("failed to write build manifest")?;
+        }
 
         Ok(ExitCode::SUCCESS)
     }
@@ -21,6 +21,8 @@ pub enum DateTimeError {
         min: String,
         max: String,
     },
+    #[error("invalid format string '{0}'")]
+    InvalidFormat(String),
     #[error(transparent)]
     ParseError(#[from] chrono::ParseError),
 }
@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt::Write};
 
 use mlua::prelude::*;
 
@@ -136,16 +136,21 @@ impl DateTime {
 
         `format` and `locale` default to `"%Y-%m-%d %H:%M:%S"` and `"en"` respectively.
 
+        Errors if the format string contains invalid or unsupported specifiers.
+
         See [`chrono_lc::DateTime::formatl`] for additional details.
     */
-    pub fn format_string_local(&self, format: Option<&str>, locale: Option<&str>) -> String {
-        self.inner
+    pub fn format_string_local(
+        &self,
+        format: Option<&str>,
+        locale: Option<&str>,
+    ) -> DateTimeResult<String> {
+        let format = format.unwrap_or(DEFAULT_FORMAT);
+        let formatted = self
+            .inner
             .with_timezone(&Local)
-            .formatl(
-                format.unwrap_or(DEFAULT_FORMAT),
-                locale.unwrap_or(DEFAULT_LOCALE),
-            )
-            .to_string()
+            .formatl(format, locale.unwrap_or(DEFAULT_LOCALE));
+        write_formatted(format, formatted)
     }
 
     /**
@@ -154,16 +159,21 @@ impl DateTime {
 
         `format` and `locale` default to `"%Y-%m-%d %H:%M:%S"` and `"en"` respectively.
 
+        Errors if the format string contains invalid or unsupported specifiers.
+
         See [`chrono_lc::DateTime::formatl`] for additional details.
     */
-    pub fn format_string_universal(&self, format: Option<&str>, locale: Option<&str>) -> String {
-        self.inner
+    pub fn format_string_universal(
+        &self,
+        format: Option<&str>,
+        locale: Option<&str>,
+    ) -> DateTimeResult<String> {
+        let format = format.unwrap_or(DEFAULT_FORMAT);
+        let formatted = self
+            .inner
             .with_timezone(&Utc)
-            .formatl(
-                format.unwrap_or(DEFAULT_FORMAT),
-                locale.unwrap_or(DEFAULT_LOCALE),
-            )
-            .to_string()
+            .formatl(format, locale.unwrap_or(DEFAULT_LOCALE));
+        write_formatted(format, formatted)
     }
 
     /**
@@ -234,13 +244,13 @@ impl LuaUserData for DateTime {
         methods.add_method(
             "formatUniversalTime",
             |_, this, (format, locale): (Option<String>, Option<String>)| {
-                Ok(this.format_string_universal(format.as_deref(), locale.as_deref()))
+                Ok(this.format_string_universal(format.as_deref(), locale.as_deref())?)
             },
         );
         methods.add_method(
             "formatLocalTime",
             |_, this, (format, locale): (Option<String>, Option<String>)| {
-                Ok(this.format_string_local(format.as_deref(), locale.as_deref()))
+                Ok(this.format_string_local(format.as_deref(), locale.as_deref())?)
             },
         );
         methods.add_method("toUniversalTime", |_, this: &Self, ()| {
@@ -249,3 +259,13 @@ impl LuaUserData for DateTime {
         methods.add_method("toLocalTime", |_, this: &Self, ()| Ok(this.to_local_time()));
     }
 }
+
+/**
+    Writes out a formatted date to a string. Invalid specifiers are only
+    detected while writing, where `to_string` would panic on the error.
+*/
+fn write_formatted(format: &str, formatted: impl std::fmt::Display) -> DateTimeResult<String> {
+    let mut output = String::new();
+    write!(output, "{formatted}").map_err(|_| DateTimeError::InvalidFormat(format.to_string()))?;
+    Ok(output)
+}
@@ -6,9 +6,17 @@ use crate::{
     lune::util::TableBuilder,
     roblox::{
         self,
-        document::{Document, DocumentError, DocumentFormat, DocumentKind},
-        instance::{registry::InstanceRegistry, Instance},
+        datatypes::{
+            approx::{lua_values_approx_eq, DEFAULT_EPSILON},
+            types::{EnumItem, PhysicalProperties, Rect, UDim2, Vector2, Vector3},
+        },
+        document::{Document, DocumentError, DocumentFormat, DocumentKind, ReflectionMissBehavior},
+        instance::{
+            base::ensure_not_destroyed, data_model, registry::InstanceRegistry, Instance,
+            ModelIssue,
+        },
         reflection::Database as ReflectionDatabase,
+        shared::{instance::StrictPropertyWrites, userdata::Int16Overflow},
     },
 };
 
@@ -28,20 +36,35 @@ pub fn create(lua: &Lua) -> LuaResult<LuaTable> {
         .with_async_function("deserializeModel", deserialize_model)?
         .with_async_function("serializePlace", serialize_place)?
         .with_async_function("serializeModel", serialize_model)?
+        .with_function("mergeInto", merge_into)?
+        .with_function("modelStats", model_stats)?
+        .with_function("validateModel", validate_model)?
+        .with_function("materialProperties", material_properties)?
+        .with_function("normalIdVector", normal_id_vector)?
+        .with_function("axisVector", axis_vector)?
+        .with_function("approxEq", approx_eq)?
+        .with_function("resolveLayout", resolve_layout)?
         .with_function("getAuthCookie", get_auth_cookie)?
         .with_function("getReflectionDatabase", get_reflection_database)?
+        .with_function("version", version)?
         .with_function("implementProperty", implement_property)?
         .with_function("implementMethod", implement_method)?
+        .with_function("setStrictPropertyWrites", set_strict_property_writes)?
+        .with_function("setInt16Overflow", set_int16_overflow)?
         .build_readonly()
 }
 
 async fn deserialize_place<'lua>(
     lua: &'lua Lua,
-    contents: LuaString<'lua>,
+    (contents, on_reflection_miss): (LuaString<'lua>, Option<ReflectionMissBehavior>),
 ) -> LuaResult<LuaValue<'lua>> {
     let bytes = contents.as_bytes().to_vec();
     let fut = lua.spawn_blocking(move || {
-        let doc = Document::from_bytes(bytes, DocumentKind::Place)?;
+        let doc = Document::from_bytes_with_reflection_misses(
+            bytes,
+            DocumentKind::Place,
+            on_reflection_miss.unwrap_or_default(),
+        )?;
         let data_model = doc.into_data_model_instance()?;
         Ok::<_, DocumentError>(data_model)
     });
@@ -50,11 +73,15 @@ async fn deserialize_place<'lua>(
 
 async fn deserialize_model<'lua>(
     lua: &'lua Lua,
-    contents: LuaString<'lua>,
+    (contents, on_reflection_miss): (LuaString<'lua>, Option<ReflectionMissBehavior>),
 ) -> LuaResult<LuaValue<'lua>> {
     let bytes = contents.as_bytes().to_vec();
     let fut = lua.spawn_blocking(move || {
-        let doc = Document::from_bytes(bytes, DocumentKind::Model)?;
+        let doc = Document::from_bytes_with_reflection_misses(
+            bytes,
+            DocumentKind::Model,
+            on_reflection_miss.unwrap_or_default(),
+        )?;
         let instance_array = doc.into_instance_array()?;
         Ok::<_, DocumentError>(instance_array)
     });
@@ -80,11 +107,18 @@ async fn serialize_place<'lua>(
 
 async fn serialize_model<'lua>(
     lua: &'lua Lua,
-    (instances, as_xml): (Vec<LuaUserDataRef<'lua, Instance>>, Option<bool>),
+    (instances, as_xml, strip_defaults): (
+        Vec<LuaUserDataRef<'lua, Instance>>,
+        Option<bool>,
+        Option<bool>,
+    ),
 ) -> LuaResult<LuaString<'lua>> {
     let instances = instances.iter().map(|i| (*i).clone()).collect();
     let fut = lua.spawn_blocking(move || {
-        let doc = Document::from_instance_array(instances)?;
+        let mut doc = Document::from_instance_array(instances)?;
+        if strip_defaults == Some(true) {
+            doc.strip_default_properties();
+        }
         let bytes = doc.to_bytes_with_format(match as_xml {
             Some(true) => DocumentFormat::Xml,
             _ => DocumentFormat::Binary,
@@ -95,6 +129,97 @@ async fn serialize_model<'lua>(
     lua.create_string(bytes)
 }
 
+fn merge_into<'lua>(
+    lua: &'lua Lua,
+    (parent, instances): (
+        LuaUserDataRef<'lua, Instance>,
+        Vec<LuaUserDataRef<'lua, Instance>>,
+    ),
+) -> LuaResult<LuaValue<'lua>> {
+    ensure_not_destroyed(&parent)?;
+    let instances = instances
+        .iter()
+        .map(|i| {
+            ensure_not_destroyed(i)?;
+            if i.get_class_name() == data_model::CLASS_NAME {
+                return Err(LuaError::RuntimeError(
+                    "Failed to merge instances - DataModel can not be reparented".to_string(),
+                ));
+            }
+            Ok((*i).clone())
+        })
+        .collect::<LuaResult<Vec<_>>>()?;
+    Instance::clone_multiple_into(&instances, &parent).into_lua(lua)
+}
+
+fn model_stats<'lua>(
+    lua: &'lua Lua,
+    instances: Vec<LuaUserDataRef<'lua, Instance>>,
+) -> LuaResult<LuaTable<'lua>> {
+    let instances = instances
+        .iter()
+        .map(|i| {
+            ensure_not_destroyed(i)?;
+            Ok((*i).clone())
+        })
+        .collect::<LuaResult<Vec<_>>>()?;
+    let classes = Instance::count_classes(&instances);
+    TableBuilder::new(lua)?
+        .with_value("total", classes.values().sum::<usize>())?
+        .with_value("classes", lua.create_table_from(classes)?)?
+        .build()
+}
+
+fn validate_model(_: &Lua, instances: Vec<LuaUserDataRef<Instance>>) -> LuaResult<Vec<ModelIssue>> {
+    let instances = instances
+        .iter()
+        .map(|i| {
+            ensure_not_destroyed(i)?;
+            Ok((*i).clone())
+        })
+        .collect::<LuaResult<Vec<_>>>()?;
+    Ok(Instance::validate_model(&instances))
+}
+
+fn material_properties(_: &Lua, material: LuaValue) -> LuaResult<PhysicalProperties> {
+    PhysicalProperties::from_material_lua(&material)
+}
+
+fn normal_id_vector(_: &Lua, normal_id: LuaUserDataRef<EnumItem>) -> LuaResult<Vector3> {
+    Vector3::from_normal_id(&normal_id)
+}
+
+fn axis_vector(_: &Lua, axis: LuaUserDataRef<EnumItem>) -> LuaResult<Vector3> {
+    Vector3::from_axis(&axis)
+}
+
+fn approx_eq(_: &Lua, (lhs, rhs, epsilon): (LuaValue, LuaValue, Option<f32>)) -> LuaResult<bool> {
+    lua_values_approx_eq(&lhs, &rhs, epsilon.unwrap_or(DEFAULT_EPSILON))
+}
+
+fn resolve_layout(
+    _: &Lua,
+    (root_size, elements): (LuaUserDataRef<Vector2>, Vec<LuaTable>),
+) -> LuaResult<Vec<Rect>> {
+    elements
+        .iter()
+        .enumerate()
+        .map(|(index, element)| {
+            let get_udim2 = |key: &str| {
+                element.get::<_, LuaUserDataRef<UDim2>>(key).map_err(|_| {
+                    LuaError::RuntimeError(format!(
+                        "Failed to resolve layout - element #{} is missing a {key} UDim2",
+                        index + 1
+                    ))
+                })
+            };
+            let position = get_udim2("position")?.resolve(root_size.0);
+            let size = get_udim2("size")?.resolve(root_size.0);
+            Ok(Rect::new(position, position + size))
+        })
+        .collect()
+}
+
 fn get_auth_cookie(_: &Lua, raw: Option<bool>) -> LuaResult<Option<String>> {
     if matches!(raw, Some(true)) {
         Ok(rbx_cookie::get_value())
@@ -107,6 +232,14 @@ fn get_reflection_database(_: &Lua, _: ()) -> LuaResult<ReflectionDatabase> {
     Ok(*REFLECTION_DATABASE.get_or_init(ReflectionDatabase::new))
 }
 
+fn version<'lua>(lua: &'lua Lua, _: ()) -> LuaResult<LuaTable<'lua>> {
+    let database = REFLECTION_DATABASE.get_or_init(ReflectionDatabase::new);
+    TableBuilder::new(lua)?
+        .with_value("version", env!("CARGO_PKG_VERSION"))?
+        .with_value("reflectionVersion", database.get_version())?
+        .build_readonly()
+}
+
 fn implement_property(
     lua: &Lua,
     (class_name, property_name, property_getter, property_setter): (
@@ -141,3 +274,13 @@ fn implement_method(
     InstanceRegistry::insert_method(lua, &class_name, &method_name, method).into_lua_err()?;
     Ok(())
 }
+
+fn set_strict_property_writes(lua: &Lua, enabled: bool) -> LuaResult<()> {
+    lua.set_app_data(StrictPropertyWrites(enabled));
+    Ok(())
+}
+
+fn set_int16_overflow(lua: &Lua, mode: Int16Overflow) -> LuaResult<()> {
+    Int16Overflow::set(lua, mode);
+    Ok(())
+}
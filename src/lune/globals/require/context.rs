@@ -27,6 +27,21 @@ pub(super) struct RequireContext {
     cache_builtins: Arc<AsyncMutex<HashMap<LuneBuiltin, LuaResult<LuaRegistryKey>>>>,
     cache_results: Arc<AsyncMutex<HashMap<PathBuf, LuaResult<LuaRegistryKey>>>>,
     cache_pending: Arc<AsyncMutex<HashMap<PathBuf, Sender<()>>>>,
+    bundle: Option<Arc<RequireBundle>>,
+}
+
+/**
+    Modules bundled into a standalone binary, keyed by their path relative
+    to the main script, with `/` as the separator regardless of platform.
+
+    Relative paths that are not bundled are resolved against the root
+    directory, instead of the current working directory, since the
+    same relative paths were resolved against the main script at build time.
+*/
+#[derive(Debug)]
+struct RequireBundle {
+    root: PathBuf,
+    modules: HashMap<String, Vec<u8>>,
 }
 
 impl RequireContext {
@@ -42,16 +57,26 @@ impl RequireContext {
             cache_builtins: Arc::new(AsyncMutex::new(HashMap::new())),
             cache_results: Arc::new(AsyncMutex::new(HashMap::new())),
             cache_pending: Arc::new(AsyncMutex::new(HashMap::new())),
+            bundle: None,
         }
     }
 
+    /**
+        Makes this require context load the given bundled modules from memory,
+        resolving any relative paths that are not bundled against `root`.
+    */
+    pub fn set_bundle(&mut self, root: PathBuf, modules: HashMap<String, Vec<u8>>) {
+        self.bundle = Some(Arc::new(RequireBundle { root, modules }));
+    }
+
     /**
         Resolves the given `source` and `path` into require paths
         to use, based on the current require context settings.
 
         This will resolve path segments such as `./`, `../`, ..., and
         if the resolved path is not an absolute path, will create an
-        absolute path by prepending the current working directory.
+        absolute path by prepending the current working directory,
+        or the bundle root directory if modules have been bundled.
     */
     pub fn resolve_paths(
         &self,
@@ -66,6 +91,8 @@ impl RequireContext {
         let rel_path = path_clean::clean(path);
         let abs_path = if rel_path.is_absolute() {
             rel_path.to_path_buf()
+        } else if let Some(bundle) = &self.bundle {
+            bundle.root.join(&rel_path)
         } else {
             CWD.join(&rel_path)
         };
@@ -161,9 +188,16 @@ impl RequireContext {
         let abs_path = abs_path.as_ref();
         let rel_path = rel_path.as_ref();
 
-        // Read the file at the given path, try to parse and
-        // load it into a new lua thread that we can schedule
-        let file_contents = fs::read(&abs_path).await?;
+        // Read the file at the given path, or its bundled bytecode, try
+        // to parse and load it into a new lua thread that we can schedule
+        let bundled = self.bundle.as_ref().and_then(|bundle| {
+            let key = rel_path.to_string_lossy().replace('\\', "/");
+            bundle.modules.get(&key).cloned()
+        });
+        let file_contents = match bundled {
+            Some(bytecode) => bytecode,
+            None => fs::read(&abs_path).await?,
+        };
         let file_thread = lua
             .load(file_contents)
             .set_name(rel_path.to_string_lossy().to_string());
@@ -1,3 +1,5 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use mlua::prelude::*;
 
 use crate::lune::util::TableBuilder;
@@ -9,6 +11,15 @@ mod alias;
 mod builtin;
 mod path;
 
+/**
+    Makes `require` load the given bundled modules from memory, see [`RequireContext::set_bundle`].
+*/
+pub fn set_bundle(lua: &Lua, root: PathBuf, modules: HashMap<String, Vec<u8>>) {
+    lua.app_data_mut::<RequireContext>()
+        .expect("Failed to get RequireContext from app data")
+        .set_bundle(root, modules);
+}
+
 const REQUIRE_IMPL: &str = r#"
 return require(source(), ...)
 "#;
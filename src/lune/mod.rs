@@ -1,4 +1,5 @@
 use std::{
+    path::PathBuf,
     process::ExitCode,
     rc::Rc,
     sync::{
@@ -55,6 +56,21 @@ impl Runtime {
         self
     }
 
+    /**
+        Bundles modules into the runtime, so that `require` loads them from memory.
+
+        Modules are keyed by their path relative to the main script, using `/` as the separator.
+        Any relative path that is not bundled is resolved against `root` instead of the
+        current working directory, such as the directory containing a standalone binary.
+    */
+    pub fn with_bundle<M>(self, root: impl Into<PathBuf>, modules: M) -> Self
+    where
+        M: IntoIterator<Item = (String, Vec<u8>)>,
+    {
+        globals::set_require_bundle(&self.lua, root.into(), modules.into_iter().collect());
+        self
+    }
+
     /**
         Runs a Lune script inside of the current runtime.
 
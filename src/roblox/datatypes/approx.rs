@@ -0,0 +1,224 @@
+use mlua::prelude::*;
+
+use super::types::{
+    CFrame, Color3, ColorSequence, ColorSequenceKeypoint, NumberRange, NumberSequence,
+    NumberSequenceKeypoint, PhysicalProperties, Ray, Rect, Region3, UDim, UDim2, Vector2, Vector3,
+};
+
+/**
+    The default epsilon used when comparing datatypes for approximate equality.
+*/
+pub const DEFAULT_EPSILON: f32 = 1e-5;
+
+/**
+    A datatype that can be compared with another of the same
+    kind for approximate equality, component by component.
+*/
+pub trait ApproxEq {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool;
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
+
+impl ApproxEq for Vector2 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.abs_diff_eq(other.0, epsilon)
+    }
+}
+
+impl ApproxEq for Vector3 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.abs_diff_eq(other.0, epsilon)
+    }
+}
+
+impl ApproxEq for Color3 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.r.approx_eq(&other.r, epsilon)
+            && self.g.approx_eq(&other.g, epsilon)
+            && self.b.approx_eq(&other.b, epsilon)
+    }
+}
+
+impl ApproxEq for CFrame {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.0.abs_diff_eq(other.0, epsilon)
+    }
+}
+
+impl ApproxEq for UDim {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        // Offsets are integers, only the scale can be imprecise
+        self.scale.approx_eq(&other.scale, epsilon) && self.offset == other.offset
+    }
+}
+
+impl ApproxEq for UDim2 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.x.approx_eq(&other.x, epsilon) && self.y.approx_eq(&other.y, epsilon)
+    }
+}
+
+impl ApproxEq for Rect {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.min.abs_diff_eq(other.min, epsilon) && self.max.abs_diff_eq(other.max, epsilon)
+    }
+}
+
+impl ApproxEq for NumberRange {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.min.approx_eq(&other.min, epsilon) && self.max.approx_eq(&other.max, epsilon)
+    }
+}
+
+impl ApproxEq for NumberSequenceKeypoint {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.time.approx_eq(&other.time, epsilon)
+            && self.value.approx_eq(&other.value, epsilon)
+            && self.envelope.approx_eq(&other.envelope, epsilon)
+    }
+}
+
+impl ApproxEq for NumberSequence {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        keypoints_approx_eq(&self.keypoints, &other.keypoints, epsilon)
+    }
+}
+
+impl ApproxEq for ColorSequenceKeypoint {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.time.approx_eq(&other.time, epsilon) && self.color.approx_eq(&other.color, epsilon)
+    }
+}
+
+impl ApproxEq for ColorSequence {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        keypoints_approx_eq(&self.keypoints, &other.keypoints, epsilon)
+    }
+}
+
+impl ApproxEq for PhysicalProperties {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.density.approx_eq(&other.density, epsilon)
+            && self.friction.approx_eq(&other.friction, epsilon)
+            && self
+                .friction_weight
+                .approx_eq(&other.friction_weight, epsilon)
+            && self.elasticity.approx_eq(&other.elasticity, epsilon)
+            && self
+                .elasticity_weight
+                .approx_eq(&other.elasticity_weight, epsilon)
+    }
+}
+
+impl ApproxEq for Ray {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.origin.abs_diff_eq(other.origin, epsilon)
+            && self.direction.abs_diff_eq(other.direction, epsilon)
+    }
+}
+
+impl ApproxEq for Region3 {
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.min.abs_diff_eq(other.min, epsilon) && self.max.abs_diff_eq(other.max, epsilon)
+    }
+}
+
+/**
+    Compares two lists of sequence keypoints, which are only approximately
+    equal if both have the same number of keypoints, compared in order.
+*/
+fn keypoints_approx_eq<K: ApproxEq>(lhs: &[K], rhs: &[K], epsilon: f32) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.approx_eq(r, epsilon))
+}
+
+/**
+    Implements the `equals` method for a datatype, which compares two values of it
+    exactly, same as `__eq`, or approximately if an epsilon is given.
+
+    Values of any other type are never equal, same as for `__eq`.
+*/
+pub fn userdata_impl_equals<D>(
+    _: &Lua,
+    datatype: &D,
+    (value, epsilon): (LuaValue, Option<f32>),
+) -> LuaResult<bool>
+where
+    D: LuaUserData + ApproxEq + PartialEq + 'static,
+{
+    if let Some(epsilon) = epsilon {
+        ensure_valid_epsilon(epsilon)?;
+    }
+    let LuaValue::UserData(ud) = value else {
+        return Ok(false);
+    };
+    let Ok(other) = ud.borrow::<D>() else {
+        return Ok(false);
+    };
+    Ok(match epsilon {
+        Some(epsilon) => datatype.approx_eq(&other, epsilon),
+        None => *datatype == *other,
+    })
+}
+
+/**
+    Makes sure that the given epsilon can be used for approximate comparisons.
+*/
+pub fn ensure_valid_epsilon(epsilon: f32) -> LuaResult<f32> {
+    if epsilon.is_nan() || epsilon < 0.0 {
+        Err(LuaError::RuntimeError(format!(
+            "Epsilon must be a non-negative number, got {epsilon}"
+        )))
+    } else {
+        Ok(epsilon)
+    }
+}
+
+/**
+    Compares two Lua values for approximate equality, dispatching on their datatype.
+
+    Supports numbers, `Vector2`, `Vector3`, `Color3` and `CFrame`.
+    Errors if the values are of different or unsupported datatypes.
+*/
+pub fn lua_values_approx_eq(lhs: &LuaValue, rhs: &LuaValue, epsilon: f32) -> LuaResult<bool> {
+    match (lhs, rhs) {
+        (
+            LuaValue::Integer(_) | LuaValue::Number(_),
+            LuaValue::Integer(_) | LuaValue::Number(_),
+        ) => Ok(lua_number(lhs).approx_eq(&lua_number(rhs), epsilon)),
+        (LuaValue::UserData(lhs), LuaValue::UserData(rhs)) => {
+            if let (Ok(l), Ok(r)) = (lhs.borrow::<Vector2>(), rhs.borrow::<Vector2>()) {
+                Ok(l.approx_eq(&r, epsilon))
+            } else if let (Ok(l), Ok(r)) = (lhs.borrow::<Vector3>(), rhs.borrow::<Vector3>()) {
+                Ok(l.approx_eq(&r, epsilon))
+            } else if let (Ok(l), Ok(r)) = (lhs.borrow::<Color3>(), rhs.borrow::<Color3>()) {
+                Ok(l.approx_eq(&r, epsilon))
+            } else if let (Ok(l), Ok(r)) = (lhs.borrow::<CFrame>(), rhs.borrow::<CFrame>()) {
+                Ok(l.approx_eq(&r, epsilon))
+            } else {
+                Err(LuaError::RuntimeError(
+                    "Expected two values of the same datatype, one of \
+                     number, Vector2, Vector3, Color3 or CFrame"
+                        .to_string(),
+                ))
+            }
+        }
+        _ => Err(LuaError::RuntimeError(format!(
+            "Can not compare {} with {} for approximate equality",
+            lhs.type_name(),
+            rhs.type_name()
+        ))),
+    }
+}
+
+fn lua_number(value: &LuaValue) -> f32 {
+    match value {
+        LuaValue::Integer(i) => *i as f32,
+        LuaValue::Number(n) => *n as f32,
+        _ => unreachable!("value must be a number"),
+    }
+}
@@ -23,33 +23,44 @@ pub fn ensure_valid_attribute_name(name: impl AsRef<str>) -> LuaResult<()> {
     }
 }
 
+/**
+    All of the datatypes that may be stored in an attribute, in alphabetical order.
+
+    This matches the set of attribute types supported by Roblox, as well as
+    the ones that may be serialized to and deserialized from model & place files.
+*/
+pub const SUPPORTED_ATTRIBUTE_TYPES: &[DomType] = &[
+    DomType::Bool,
+    DomType::BrickColor,
+    DomType::CFrame,
+    DomType::Color3,
+    DomType::ColorSequence,
+    DomType::Float32,
+    DomType::Float64,
+    DomType::Font,
+    DomType::Int32,
+    DomType::Int64,
+    DomType::NumberRange,
+    DomType::NumberSequence,
+    DomType::Rect,
+    DomType::String,
+    DomType::UDim,
+    DomType::UDim2,
+    DomType::Vector2,
+    DomType::Vector3,
+];
+
 pub fn ensure_valid_attribute_value(value: &DomValue) -> LuaResult<()> {
-    let is_valid = matches!(
-        value.ty(),
-        DomType::Bool
-            | DomType::BrickColor
-            | DomType::CFrame
-            | DomType::Color3
-            | DomType::ColorSequence
-            | DomType::Float32
-            | DomType::Float64
-            | DomType::Font
-            | DomType::Int32
-            | DomType::Int64
-            | DomType::NumberRange
-            | DomType::NumberSequence
-            | DomType::Rect
-            | DomType::String
-            | DomType::UDim
-            | DomType::UDim2
-            | DomType::Vector2
-            | DomType::Vector3
-    );
-    if is_valid {
+    if SUPPORTED_ATTRIBUTE_TYPES.contains(&value.ty()) {
         Ok(())
     } else {
+        let supported = SUPPORTED_ATTRIBUTE_TYPES
+            .iter()
+            .filter_map(|ty| ty.variant_name())
+            .collect::<Vec<_>>()
+            .join(", ");
         Err(LuaError::RuntimeError(format!(
-            "'{}' is not a valid attribute type",
+            "'{}' is not a valid attribute type, expected one of: {supported}",
             value.ty().variant_name().unwrap_or("???")
         )))
     }
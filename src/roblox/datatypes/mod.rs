@@ -1,5 +1,6 @@
 pub(crate) use rbx_dom_weak::types::{Variant as DomValue, VariantType as DomType};
 
+pub mod approx;
 pub mod attributes;
 pub mod conversion;
 pub mod extension;
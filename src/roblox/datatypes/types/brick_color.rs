@@ -1,10 +1,12 @@
 use core::fmt;
 
 use mlua::prelude::*;
-use rand::seq::SliceRandom;
 use rbx_dom_weak::types::BrickColor as DomBrickColor;
 
-use crate::{lune::util::TableBuilder, roblox::exports::LuaExportsTable};
+use crate::{
+    lune::util::TableBuilder,
+    roblox::{exports::LuaExportsTable, shared::random::SeededRandom},
+};
 
 use super::{super::*, Color3};
 
@@ -58,9 +60,10 @@ impl LuaExportsTable<'_> for BrickColor {
             }
         };
 
-        let brick_color_random = |_, ()| {
-            let number = BRICK_COLOR_PALETTE.choose(&mut rand::thread_rng());
-            Ok(color_from_number(*number.unwrap()))
+        let brick_color_random = |_, seed: Option<f64>| {
+            let mut rng = SeededRandom::from_lua_seed(seed)?;
+            let index = rng.next_u64() % BRICK_COLOR_PALETTE.len() as u64;
+            Ok(color_from_number(BRICK_COLOR_PALETTE[index as usize]))
         };
 
         let mut builder = TableBuilder::new(lua)?
@@ -110,9 +113,15 @@ impl fmt::Display for BrickColor {
 
 impl From<Color3> for BrickColor {
     fn from(value: Color3) -> Self {
-        let r = value.r.clamp(u8::MIN as f32, u8::MAX as f32) as u8;
-        let g = value.g.clamp(u8::MIN as f32, u8::MAX as f32) as u8;
-        let b = value.b.clamp(u8::MIN as f32, u8::MAX as f32) as u8;
+        let r = (value.r * 255.0)
+            .round()
+            .clamp(u8::MIN as f32, u8::MAX as f32) as u8;
+        let g = (value.g * 255.0)
+            .round()
+            .clamp(u8::MIN as f32, u8::MAX as f32) as u8;
+        let b = (value.b * 255.0)
+            .round()
+            .clamp(u8::MIN as f32, u8::MAX as f32) as u8;
         color_from_rgb(r, g, b)
     }
 }
@@ -180,18 +189,18 @@ fn color_from_name(name: impl AsRef<str>) -> BrickColor {
 }
 
 fn color_from_rgb(r: u8, g: u8, b: u8) -> BrickColor {
-    let r = r as i16;
-    let g = g as i16;
-    let b = b as i16;
+    let r = r as i32;
+    let g = g as i32;
+    let b = b as i32;
     BRICK_COLOR_VALUES
         .iter()
         .fold(
-            (None, u16::MAX),
+            (None, u32::MAX),
             |(closest_color, closest_distance), color| {
-                let cr = color.2 .0 as i16;
-                let cg = color.2 .1 as i16;
-                let cb = color.2 .2 as i16;
-                let distance = ((r - cr) + (g - cg) + (b - cb)).unsigned_abs();
+                let dr = r - color.2 .0 as i32;
+                let dg = g - color.2 .1 as i32;
+                let db = b - color.2 .2 as i32;
+                let distance = (dr * dr + dg * dg + db * db) as u32;
                 if distance < closest_distance {
                     (Some(color), distance)
                 } else {
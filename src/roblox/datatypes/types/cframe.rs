@@ -5,9 +5,18 @@ use glam::{EulerRot, Mat3, Mat4, Quat, Vec3};
 use mlua::{prelude::*, Variadic};
 use rbx_dom_weak::types::{CFrame as DomCFrame, Matrix3 as DomMatrix3, Vector3 as DomVector3};
 
-use crate::{lune::util::TableBuilder, roblox::exports::LuaExportsTable};
-
-use super::{super::*, Vector3};
+use crate::{
+    lune::util::TableBuilder,
+    roblox::{
+        exports::LuaExportsTable,
+        shared::buffer::{ensure_buffer_vector3s, read_vector3s, write_vector3s},
+    },
+};
+
+use super::{
+    super::{approx::ApproxEq, *},
+    Ray, Vector3,
+};
 
 /**
     An implementation of the [CFrame](https://create.roblox.com/docs/reference/engine/datatypes/CFrame)
@@ -34,9 +43,104 @@ impl CFrame {
         )
     }
 
+    /**
+        Creates a unit ray from this camera transform through a point on the viewport.
+
+        The field of view is vertical and in degrees, and the aspect ratio is width / height.
+        The viewport point is in normalized device coordinates, where `(0, 0)` is the center
+        of the viewport, `(-1, -1)` the bottom left corner, and `(1, 1)` the top right corner.
+        The camera looks along its `LookVector`, same as a `Camera` would in Roblox.
+    */
+    fn viewport_point_to_ray(
+        &self,
+        fov_y: f32,
+        aspect: f32,
+        ndc_x: f32,
+        ndc_y: f32,
+    ) -> LuaResult<Ray> {
+        if !(fov_y > 0.0 && fov_y < 180.0) {
+            return Err(LuaError::RuntimeError(format!(
+                "Field of view must be between 0 and 180 degrees, got {fov_y}"
+            )));
+        }
+        if !(aspect > 0.0 && aspect.is_finite()) {
+            return Err(LuaError::RuntimeError(format!(
+                "Aspect ratio must be a positive number, got {aspect}"
+            )));
+        }
+        let half_height = (fov_y.to_radians() / 2.0).tan();
+        let local = Vec3::new(ndc_x * half_height * aspect, ndc_y * half_height, -1.0);
+        Ok(Ray {
+            origin: self.position(),
+            direction: (self.orientation() * local).normalize(),
+        })
+    }
+
+    fn rotation(&self) -> Self {
+        Self(Mat4::from_cols(
+            self.0.x_axis,
+            self.0.y_axis,
+            self.0.z_axis,
+            Vec3::ZERO.extend(1.0),
+        ))
+    }
+
     fn inverse(&self) -> Self {
         Self(self.0.inverse())
     }
+
+    /**
+        Transforms many points into the object space of this CFrame at once,
+        which only needs to compute the inverse of this CFrame a single time.
+    */
+    fn points_to_object_space(&self, points: &[Vec3]) -> Vec<Vec3> {
+        let inverse = self.inverse().0;
+        points
+            .iter()
+            .map(|point| inverse.project_point3(*point))
+            .collect()
+    }
+
+    /**
+        Transforms many points into the object space of this CFrame at once, given
+        either as an array of `Vector3`s, or a buffer of tightly packed `f32` triples.
+
+        Returns a new array or buffer, matching the kind of value that was given.
+    */
+    fn points_to_object_space_lua<'lua>(
+        &self,
+        lua: &'lua Lua,
+        points: LuaValue<'lua>,
+    ) -> LuaResult<LuaValue<'lua>> {
+        match &points {
+            LuaValue::Table(tab) => {
+                let points = tab
+                    .clone()
+                    .sequence_values::<LuaUserDataRef<Vector3>>()
+                    .map(|point| point.map(|p| p.0))
+                    .collect::<LuaResult<Vec<_>>>()?;
+                let transformed = self.points_to_object_space(&points);
+                lua.create_sequence_from(transformed.into_iter().map(Vector3))?
+                    .into_lua(lua)
+            }
+            _ if points.type_name() == "buffer" => {
+                let (buffer_lib, bytes) = ensure_buffer_vector3s(lua, points)?;
+                let points = read_vector3s(bytes.as_bytes()).collect::<Vec<_>>();
+                let transformed = write_vector3s(self.points_to_object_space(&points));
+                buffer_lib
+                    .get::<_, LuaFunction>("fromstring")?
+                    .call(lua.create_string(transformed)?)
+            }
+            _ => Err(LuaError::FromLuaConversionError {
+                from: points.type_name(),
+                to: "Vector3",
+                message: Some(format!(
+                    "Expected array of Vector3 or buffer, got {}",
+                    points.type_name()
+                )),
+            }),
+        }
+    }
 }
 
 impl LuaExportsTable<'_> for CFrame {
@@ -47,6 +151,16 @@ impl LuaExportsTable<'_> for CFrame {
             Ok(CFrame(Mat4::from_euler(EulerRot::XYZ, rx, ry, rz)))
         };
 
+        let cframe_compose = |_, cframes: Vec<LuaUserDataRef<CFrame>>| {
+            if cframes.is_empty() {
+                return Err(LuaError::RuntimeError(
+                    "CFrame.compose requires at least 1 CFrame".to_string(),
+                ));
+            }
+            Ok(cframes
+                .iter()
+                .fold(CFrame(Mat4::IDENTITY), |acc, cframe| acc * **cframe))
+        };
         let cframe_from_axis_angle =
             |_, (v, r): (LuaUserDataRef<Vector3>, f32)| Ok(CFrame(Mat4::from_axis_angle(v.0, r)));
 
@@ -92,6 +206,27 @@ impl LuaExportsTable<'_> for CFrame {
             )))
         };
 
+        let cframe_from_look_at = |_,
+                                   (eye, target, roll): (
+            LuaUserDataRef<Vector3>,
+            LuaUserDataRef<Vector3>,
+            Option<f32>,
+        )| {
+            let dir = target.0 - eye.0;
+            let base = if dir.length_squared() <= f32::EPSILON {
+                // Looking at the eye position itself has no direction,
+                // so we keep the identity orientation at the eye instead
+                Mat4::from_translation(eye.0)
+            } else if dir.normalize().cross(Vec3::Y).length_squared() <= f32::EPSILON {
+                // Looking straight up or down would make the up vector
+                // parallel to the look direction, use another up vector
+                look_at(eye.0, target.0, Vec3::Z)
+            } else {
+                look_at(eye.0, target.0, Vec3::Y)
+            };
+            Ok(CFrame(base * Mat4::from_rotation_z(roll.unwrap_or(0.0))))
+        };
+
         // Dynamic args constructor
         type ArgsPos<'lua> = LuaUserDataRef<'lua, Vector3>;
         type ArgsLook<'lua> = (
@@ -158,9 +293,11 @@ impl LuaExportsTable<'_> for CFrame {
         TableBuilder::new(lua)?
             .with_function("Angles", cframe_angles)?
             .with_value("identity", CFrame(Mat4::IDENTITY))?
+            .with_function("compose", cframe_compose)?
             .with_function("fromAxisAngle", cframe_from_axis_angle)?
             .with_function("fromEulerAnglesXYZ", cframe_from_euler_angles_xyz)?
             .with_function("fromEulerAnglesYXZ", cframe_from_euler_angles_yxz)?
+            .with_function("fromLookAt", cframe_from_look_at)?
             .with_function("fromMatrix", cframe_from_matrix)?
             .with_function("fromOrientation", cframe_from_orientation)?
             .with_function("lookAt", cframe_look_at)?
@@ -172,14 +309,7 @@ impl LuaExportsTable<'_> for CFrame {
 impl LuaUserData for CFrame {
     fn add_fields<'lua, F: LuaUserDataFields<'lua, Self>>(fields: &mut F) {
         fields.add_field_method_get("Position", |_, this| Ok(Vector3(this.position())));
-        fields.add_field_method_get("Rotation", |_, this| {
-            Ok(CFrame(Mat4::from_cols(
-                this.0.x_axis,
-                this.0.y_axis,
-                this.0.z_axis,
-                Vec3::ZERO.extend(1.0),
-            )))
-        });
+        fields.add_field_method_get("Rotation", |_, this| Ok(this.rotation()));
         fields.add_field_method_get("X", |_, this| Ok(this.position().x));
         fields.add_field_method_get("Y", |_, this| Ok(this.position().y));
         fields.add_field_method_get("Z", |_, this| Ok(this.position().z));
@@ -197,10 +327,29 @@ impl LuaUserData for CFrame {
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         // Methods
+        methods.add_method("combine", |_, this, offset: LuaUserDataRef<CFrame>| {
+            Ok(*this * *offset)
+        });
+        methods.add_method("getPosition", |_, this, ()| Ok(Vector3(this.position())));
+        methods.add_method("GetRotation", |_, this, ()| Ok(this.rotation()));
+        methods.add_method("getRotationQuaternion", |_, this, ()| {
+            let [qx, qy, qz, qw] = Quat::from_mat4(&this.0).normalize().to_array();
+            Ok((qx, qy, qz, qw))
+        });
         methods.add_method("Inverse", |_, this, ()| Ok(this.inverse()));
+        methods.add_method("isApproxIdentity", |_, this, epsilon: Option<f32>| {
+            let epsilon = approx::ensure_valid_epsilon(epsilon.unwrap_or(approx::DEFAULT_EPSILON))?;
+            Ok(this.approx_eq(&CFrame::IDENTITY, epsilon))
+        });
         methods.add_method(
             "Lerp",
-            |_, this, (goal, alpha): (LuaUserDataRef<CFrame>, f32)| {
+            |_, this, (goal, alpha, clamp): (LuaUserDataRef<CFrame>, f32, Option<bool>)| {
+                // Roblox extrapolates past the goal, clamping is opt-in
+                let alpha = if clamp.unwrap_or_default() {
+                    alpha.clamp(0.0, 1.0)
+                } else {
+                    alpha
+                };
                 let quat_this = Quat::from_mat4(&this.0);
                 let quat_goal = Quat::from_mat4(&goal.0);
                 let translation = this
@@ -223,6 +372,9 @@ impl LuaUserData for CFrame {
                 translation,
             )))
         });
+        methods.add_method("relativeTo", |_, this, other: LuaUserDataRef<CFrame>| {
+            Ok(other.inverse() * *this)
+        });
         methods.add_method(
             "ToWorldSpace",
             |_, this, rhs: Variadic<LuaUserDataRef<CFrame>>| {
@@ -249,6 +401,16 @@ impl LuaUserData for CFrame {
                 Ok(Variadic::from_iter(rhs.into_iter().map(|v3| inverse * *v3)))
             },
         );
+        methods.add_method("pointsToObjectSpace", |lua, this, points: LuaValue| {
+            this.points_to_object_space_lua(lua, points)
+        });
+        methods.add_method(
+            "vectorToObjectSpace",
+            |_, this, v: LuaUserDataRef<Vector3>| {
+                // The inverse of a rotation matrix is its transpose, the position does not matter
+                Ok(Vector3(this.orientation().transpose() * v.0))
+            },
+        );
         methods.add_method(
             "VectorToWorldSpace",
             |_, this, rhs: Variadic<LuaUserDataRef<Vector3>>| {
@@ -291,6 +453,22 @@ impl LuaUserData for CFrame {
             let (axis, angle) = Quat::from_mat4(&this.0).to_axis_angle();
             Ok((Vector3(axis), angle))
         });
+        methods.add_method("worldOrientation", |_, this, ()| {
+            // Same rotation order and units as the Orientation property of parts
+            let (ry, rx, rz) = Quat::from_mat4(&this.0).to_euler(EulerRot::YXZ);
+            Ok(Vector3(Vec3::new(
+                rx.to_degrees(),
+                ry.to_degrees(),
+                rz.to_degrees(),
+            )))
+        });
+        methods.add_method(
+            "viewportPointToRay",
+            |_, this, (fov_y, aspect, ndc_x, ndc_y): (f32, f32, f32, f32)| {
+                this.viewport_point_to_ray(fov_y, aspect, ndc_x, ndc_y)
+            },
+        );
+        methods.add_method("equals", approx::userdata_impl_equals);
         // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
@@ -389,13 +567,16 @@ impl From<DomCFrame> for CFrame {
 
 impl From<CFrame> for DomCFrame {
     fn from(v: CFrame) -> Self {
+        // NOTE: The rotation basis is stored as-is, rounding it the same way as
+        // positions would skew every rotated CFrame that gets written to a file
         let transposed = v.orientation().transpose();
+        let row = |row: Vec3| DomVector3::new(row.x, row.y, row.z);
         DomCFrame {
             position: DomVector3::from(Vector3(v.position())),
             orientation: DomMatrix3::new(
-                DomVector3::from(Vector3(transposed.x_axis)),
-                DomVector3::from(Vector3(transposed.y_axis)),
-                DomVector3::from(Vector3(transposed.z_axis)),
+                row(transposed.x_axis),
+                row(transposed.y_axis),
+                row(transposed.z_axis),
             ),
         }
     }
@@ -409,23 +590,84 @@ impl From<CFrame> for DomCFrame {
 */
 fn look_at(from: Vec3, to: Vec3, up: Vec3) -> Mat4 {
     let dir = (to - from).normalize();
-    let xaxis = up.cross(dir).normalize();
-    let yaxis = dir.cross(xaxis).normalize();
+    let xaxis = dir.cross(up).normalize();
+    let yaxis = xaxis.cross(dir).normalize();
 
     Mat4::from_cols(
-        Vec3::new(xaxis.x, yaxis.x, dir.x).extend(0.0),
-        Vec3::new(xaxis.y, yaxis.y, dir.y).extend(0.0),
-        Vec3::new(xaxis.z, yaxis.z, dir.z).extend(0.0),
+        xaxis.extend(0.0),
+        yaxis.extend(0.0),
+        (-dir).extend(0.0),
         from.extend(1.0),
     )
 }
 
 #[cfg(test)]
 mod cframe_test {
-    use glam::{Mat4, Vec3};
+    use std::time::Instant;
+
+    use glam::{EulerRot, Mat4, Vec3};
     use rbx_dom_weak::types::{CFrame as DomCFrame, Matrix3 as DomMatrix3, Vector3 as DomVector3};
 
-    use super::CFrame;
+    use super::{CFrame, Vector3};
+
+    fn sample_cframe() -> CFrame {
+        CFrame(
+            Mat4::from_translation(Vec3::new(4.0, -2.0, 7.5))
+                * Mat4::from_euler(EulerRot::YXZ, 0.7, -0.3, 1.1),
+        )
+    }
+
+    fn sample_points(count: usize) -> Vec<Vec3> {
+        (0..count)
+            .map(|i| {
+                let i = i as f32;
+                Vec3::new(i * 0.5, 10.0 - i, (i * 0.25).sin() * 3.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn points_to_object_space_matches_single() {
+        let cframe = sample_cframe();
+        let points = sample_points(64);
+
+        let batched = cframe.points_to_object_space(&points);
+        assert_eq!(batched.len(), points.len());
+
+        for (point, transformed) in points.iter().zip(batched) {
+            let single = cframe.inverse() * Vector3(*point);
+            assert!(single.0.abs_diff_eq(transformed, 1e-5));
+            assert!((cframe * Vector3(transformed)).0.abs_diff_eq(*point, 1e-4));
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn points_to_object_space_benchmark() {
+        const ITERATIONS: usize = 1_000;
+
+        let cframe = sample_cframe();
+        let points = sample_points(1_000);
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let transformed = points
+                .iter()
+                .map(|point| cframe.inverse() * Vector3(*point))
+                .collect::<Vec<_>>();
+            assert_eq!(transformed.len(), points.len());
+        }
+        let per_point = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let transformed = cframe.points_to_object_space(&points);
+            assert_eq!(transformed.len(), points.len());
+        }
+        let batched = start.elapsed();
+
+        println!("per-point: {per_point:?}, batched: {batched:?}");
+    }
 
     #[test]
     fn dom_cframe_from_cframe() {
@@ -468,4 +710,19 @@ mod cframe_test {
 
         assert_eq!(DomCFrame::from(cframe), dom_cframe)
     }
+
+    #[test]
+    fn dom_cframe_round_trip() {
+        let rotation_only = CFrame(Mat4::from_cols(
+            Vec3::Z.extend(0.0),
+            Vec3::X.extend(0.0),
+            Vec3::Y.extend(0.0),
+            Vec3::ZERO.extend(1.0),
+        ));
+
+        for cframe in [sample_cframe(), rotation_only, CFrame::IDENTITY] {
+            let round_tripped = CFrame::from(DomCFrame::from(cframe));
+            assert_eq!(round_tripped, cframe);
+        }
+    }
 }
@@ -5,9 +5,12 @@ use glam::Vec3;
 use mlua::prelude::*;
 use rbx_dom_weak::types::{Color3 as DomColor3, Color3uint8 as DomColor3uint8};
 
-use crate::{lune::util::TableBuilder, roblox::exports::LuaExportsTable};
+use crate::{
+    lune::util::TableBuilder,
+    roblox::{exports::LuaExportsTable, shared::random::SeededRandom},
+};
 
-use super::super::*;
+use super::{super::*, EnumItem, Vector3};
 
 /**
     An implementation of the [Color3](https://create.roblox.com/docs/reference/engine/datatypes/Color3) Roblox datatype.
@@ -24,6 +27,97 @@ pub struct Color3 {
     pub(crate) b: f32,
 }
 
+impl Color3 {
+    fn from_hsv_components(h: f32, s: f32, v: f32) -> Self {
+        // https://axonflux.com/handy-rgb-to-hsl-and-rgb-to-hsv-color-model-c
+        let i = (h * 6.0).floor();
+        let f = h * 6.0 - i;
+        let p = v * (1.0 - s);
+        let q = v * (1.0 - f * s);
+        let t = v * (1.0 - (1.0 - f) * s);
+
+        let (r, g, b) = match (i % 6.0) as u8 {
+            0 => (v, t, p),
+            1 => (q, v, p),
+            2 => (p, v, t),
+            3 => (p, q, v),
+            4 => (t, p, v),
+            5 => (v, p, q),
+            _ => unreachable!(),
+        };
+
+        Self { r, g, b }
+    }
+
+    fn to_hsv_components(self) -> (f32, f32, f32) {
+        // https://axonflux.com/handy-rgb-to-hsl-and-rgb-to-hsv-color-model-c
+        let (r, g, b) = (self.r, self.g, self.b);
+        let min = r.min(g).min(b);
+        let max = r.max(g).max(b);
+        let diff = max - min;
+
+        let hue = (match max {
+            max if max == min => 0.0,
+            max if max == r => (g - b) / diff + (if g < b { 6.0 } else { 0.0 }),
+            max if max == g => (b - r) / diff + 2.0,
+            max if max == b => (r - g) / diff + 4.0,
+            _ => unreachable!(),
+        }) / 6.0;
+
+        let sat = if max == 0.0 {
+            0.0
+        } else {
+            (diff / max).clamp(0.0, 1.0)
+        };
+
+        (hue, sat, max)
+    }
+
+    /**
+        Gets the channels of this color as 0-255 integers,
+        rounded to the nearest integer and clamped to range.
+    */
+    pub(crate) fn to_rgb_u8(self) -> (u8, u8, u8) {
+        let channel = |c: f32| (c * 255.0).round().clamp(u8::MIN as f32, u8::MAX as f32) as u8;
+        (channel(self.r), channel(self.g), channel(self.b))
+    }
+
+    /**
+        Converts this color from sRGB to the CIELAB color space,
+        using the D65 standard illuminant as the reference white.
+
+        Returns the `L*`, `a*` and `b*` components, in that order.
+    */
+    pub(crate) fn to_lab(self) -> Vec3 {
+        // https://en.wikipedia.org/wiki/SRGB#From_sRGB_to_CIE_XYZ
+        let linear = |c: f32| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = (linear(self.r), linear(self.g), linear(self.b));
+
+        let x = 0.4124 * r + 0.3576 * g + 0.1805 * b;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = 0.0193 * r + 0.1192 * g + 0.9505 * b;
+
+        // https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIEXYZ_to_CIELAB
+        let f = |t: f32| {
+            const DELTA: f32 = 6.0 / 29.0;
+            if t > DELTA * DELTA * DELTA {
+                t.cbrt()
+            } else {
+                t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+            }
+        };
+        let (fx, fy, fz) = (f(x / 0.950_489), f(y), f(z / 1.088_84));
+
+        Vec3::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+}
+
 impl LuaExportsTable<'_> for Color3 {
     const EXPORT_NAME: &'static str = "Color3";
 
@@ -36,25 +130,35 @@ impl LuaExportsTable<'_> for Color3 {
             })
         };
 
-        let color3_from_hsv = |_, (h, s, v): (f32, f32, f32)| {
+        let color3_from_rgba =
+            move |lua, (r, g, b, a): (Option<u8>, Option<u8>, Option<u8>, Option<f32>)| {
+                // Color3 has no alpha channel, so it gets passed through separately
+                let color = color3_from_rgb(lua, (r, g, b))?;
+                Ok((color, ensure_unit_alpha(a.unwrap_or(1.0))?))
+            };
+
+        let color3_from_hsv =
+            |_, (h, s, v): (f32, f32, f32)| Ok(Color3::from_hsv_components(h, s, v));
+
+        let color3_from_hsl = |_, (h, s, l): (f32, f32, f32)| {
             // https://axonflux.com/handy-rgb-to-hsl-and-rgb-to-hsv-color-model-c
-            let i = (h * 6.0).floor();
-            let f = h * 6.0 - i;
-            let p = v * (1.0 - s);
-            let q = v * (1.0 - f * s);
-            let t = v * (1.0 - (1.0 - f) * s);
-
-            let (r, g, b) = match (i % 6.0) as u8 {
-                0 => (v, t, p),
-                1 => (q, v, p),
-                2 => (p, v, t),
-                3 => (p, q, v),
-                4 => (t, p, v),
-                5 => (v, p, q),
-                _ => unreachable!(),
+            // Hue, saturation & lightness are all in the range 0..1
+            if s == 0.0 {
+                return Ok(Color3 { r: l, g: l, b: l });
+            }
+
+            let q = if l < 0.5 {
+                l * (1.0 + s)
+            } else {
+                l + s - l * s
             };
+            let p = 2.0 * l - q;
 
-            Ok(Color3 { r, g, b })
+            Ok(Color3 {
+                r: hue_to_rgb(p, q, h + 1.0 / 3.0),
+                g: hue_to_rgb(p, q, h),
+                b: hue_to_rgb(p, q, h - 1.0 / 3.0),
+            })
         };
 
         let color3_from_hex = |_, hex: String| {
@@ -91,6 +195,42 @@ impl LuaExportsTable<'_> for Color3 {
             }
         };
 
+        let color3_from_int = |_, n: u32| {
+            let n = n & 0xFF_FF_FF;
+            Ok(Color3 {
+                r: ((n >> 16) & 0xFF) as f32 / 255f32,
+                g: ((n >> 8) & 0xFF) as f32 / 255f32,
+                b: (n & 0xFF) as f32 / 255f32,
+            })
+        };
+
+        let color3_mix = |_, (colors, weights): (Vec<LuaUserDataRef<Color3>>, Vec<f32>)| {
+            if colors.len() != weights.len() {
+                return Err(LuaError::RuntimeError(format!(
+                    "Expected the same amount of colors and weights, got {} colors and {} weights",
+                    colors.len(),
+                    weights.len()
+                )));
+            }
+            if let Some(weight) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+                return Err(LuaError::RuntimeError(format!(
+                    "Weights must be finite non-negative numbers, got {weight}"
+                )));
+            }
+            let total = weights.iter().sum::<f32>();
+            if total <= 0.0 {
+                return Err(LuaError::RuntimeError(
+                    "Weights must add up to more than zero".to_string(),
+                ));
+            }
+            Ok(colors
+                .iter()
+                .zip(weights)
+                .fold(Color3::default(), |mixed, (color, weight)| {
+                    mixed + **color * (weight / total)
+                }))
+        };
+
         let color3_new = |_, (r, g, b): (Option<f32>, Option<f32>, Option<f32>)| {
             Ok(Color3 {
                 r: r.unwrap_or_default(),
@@ -99,11 +239,42 @@ impl LuaExportsTable<'_> for Color3 {
             })
         };
 
+        let color3_unpremultiply = |_, (color, alpha): (LuaUserDataRef<Color3>, f32)| {
+            // Fully transparent colors have lost their channels, so there is
+            // nothing sensible to recover here, and dividing would give us NaN
+            if alpha.abs() <= f32::EPSILON {
+                Ok(Color3::default())
+            } else {
+                Ok(*color / alpha)
+            }
+        };
+
+        let color3_random = |_, options: Option<Color3RandomOptions>| {
+            let options = options.unwrap_or_default();
+            let mut rng = SeededRandom::from_lua_seed(options.seed)?;
+            let hue = if options.min_hue <= options.max_hue {
+                options.min_hue + rng.next_f32() * (options.max_hue - options.min_hue)
+            } else {
+                // The hue range wraps around, such as from magenta to yellow through red
+                let span = 1.0 - options.min_hue + options.max_hue;
+                (options.min_hue + rng.next_f32() * span).rem_euclid(1.0)
+            };
+            let saturation = lerp_unit(options.min_saturation, rng.next_f32());
+            let value = lerp_unit(options.min_value, rng.next_f32());
+            Ok(Color3::from_hsv_components(hue, saturation, value))
+        };
+
         TableBuilder::new(lua)?
             .with_function("fromRGB", color3_from_rgb)?
+            .with_function("fromRGBA", color3_from_rgba)?
             .with_function("fromHSV", color3_from_hsv)?
+            .with_function("fromHSL", color3_from_hsl)?
             .with_function("fromHex", color3_from_hex)?
+            .with_function("fromInt", color3_from_int)?
+            .with_function("mix", color3_mix)?
             .with_function("new", color3_new)?
+            .with_function("random", color3_random)?
+            .with_function("unpremultiply", color3_unpremultiply)?
             .build_readonly()
     }
 }
@@ -131,6 +302,24 @@ impl LuaUserData for Color3 {
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         // Methods
+        methods.add_method("bgr", |_, this, ()| {
+            Ok(Vector3(Vec3::new(this.b, this.g, this.r)))
+        });
+        methods.add_method(
+            "blend",
+            |_, this, (rhs, mode): (LuaUserDataRef<Color3>, Color3BlendMode)| {
+                Ok(Color3 {
+                    r: mode.blend(this.r, rhs.r),
+                    g: mode.blend(this.g, rhs.g),
+                    b: mode.blend(this.b, rhs.b),
+                })
+            },
+        );
+        methods.add_method("deltaE", |_, this, rhs: LuaUserDataRef<Color3>| {
+            // CIE76, the euclidean distance between the two colors in CIELAB
+            Ok(this.to_lab().distance(rhs.to_lab()))
+        });
+        methods.add_method("equals", approx::userdata_impl_equals);
         methods.add_method(
             "Lerp",
             |_, this, (rhs, alpha): (LuaUserDataRef<Color3>, f32)| {
@@ -144,28 +333,52 @@ impl LuaUserData for Color3 {
                 })
             },
         );
-        methods.add_method("ToHSV", |_, this, ()| {
+        methods.add_method("premultiply", |_, this, alpha: f32| Ok(*this * alpha));
+        methods.add_method("rgb", |_, this, ()| {
+            Ok(Vector3(Vec3::new(this.r, this.g, this.b)))
+        });
+        methods.add_method("toAnsi", |_, this, background: Option<bool>| {
+            // Truecolor escape sequence, 38 sets the foreground and 48 the background
+            let (r, g, b) = this.to_rgb_u8();
+            let target = if background.unwrap_or_default() {
+                48
+            } else {
+                38
+            };
+            Ok(format!("\x1b[{target};2;{r};{g};{b}m"))
+        });
+        methods.add_method("toInt", |_, this, ()| {
+            let (r, g, b) = this.to_rgb_u8();
+            Ok((u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b))
+        });
+        methods.add_method("ToHSV", |_, this, ()| Ok(this.to_hsv_components()));
+        methods.add_method("ToHSL", |_, this, ()| {
             // https://axonflux.com/handy-rgb-to-hsl-and-rgb-to-hsv-color-model-c
+            // Hue, saturation & lightness are all in the range 0..1
             let (r, g, b) = (this.r, this.g, this.b);
             let min = r.min(g).min(b);
             let max = r.max(g).max(b);
             let diff = max - min;
+            let lightness = (max + min) / 2.0;
+
+            if max == min {
+                return Ok((0.0, 0.0, lightness));
+            }
 
             let hue = (match max {
-                max if max == min => 0.0,
                 max if max == r => (g - b) / diff + (if g < b { 6.0 } else { 0.0 }),
                 max if max == g => (b - r) / diff + 2.0,
                 max if max == b => (r - g) / diff + 4.0,
                 _ => unreachable!(),
             }) / 6.0;
 
-            let sat = if max == 0.0 {
-                0.0
+            let sat = if lightness > 0.5 {
+                diff / (2.0 - max - min)
             } else {
-                (diff / max).clamp(0.0, 1.0)
+                diff / (max + min)
             };
 
-            Ok((hue, sat, max))
+            Ok((hue, sat.clamp(0.0, 1.0), lightness))
         });
         methods.add_method("ToHex", |_, this, ()| {
             Ok(format!(
@@ -175,6 +388,25 @@ impl LuaUserData for Color3 {
                 (this.b * 255.0).clamp(u8::MIN as f32, u8::MAX as f32) as u8,
             ))
         });
+        methods.add_method("withAlpha", |_, this, alpha: f32| {
+            Ok((this.r, this.g, this.b, ensure_unit_alpha(alpha)?))
+        });
+        methods.add_method("withBrightness", |_, this, delta: f32| {
+            let (h, s, v) = this.to_hsv_components();
+            Ok(Color3::from_hsv_components(
+                h,
+                s,
+                (v + delta).clamp(0.0, 1.0),
+            ))
+        });
+        methods.add_method("withSaturation", |_, this, delta: f32| {
+            let (h, s, v) = this.to_hsv_components();
+            Ok(Color3::from_hsv_components(
+                h,
+                (s + delta).clamp(0.0, 1.0),
+                v,
+            ))
+        });
         // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
@@ -186,6 +418,150 @@ impl LuaUserData for Color3 {
     }
 }
 
+/**
+    Gets a single color channel from HSL, given the intermediate values `p` and `q`.
+*/
+fn hue_to_rgb(p: f32, q: f32, t: f32) -> f32 {
+    let t = t.rem_euclid(1.0);
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
+/**
+    Options for generating a random `Color3`, picked uniformly in HSV space.
+
+    The hue range may wrap around, if the minimum hue is larger than the maximum hue.
+*/
+#[derive(Debug, Clone, Copy)]
+struct Color3RandomOptions {
+    seed: Option<f64>,
+    min_hue: f32,
+    max_hue: f32,
+    min_saturation: f32,
+    min_value: f32,
+}
+
+impl Default for Color3RandomOptions {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            min_hue: 0.0,
+            max_hue: 1.0,
+            min_saturation: 0.0,
+            min_value: 0.0,
+        }
+    }
+}
+
+impl<'lua> FromLua<'lua> for Color3RandomOptions {
+    fn from_lua(value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
+        let LuaValue::Table(tab) = value else {
+            return Err(LuaError::FromLuaConversionError {
+                from: value.type_name(),
+                to: "Color3RandomOptions",
+                message: Some(format!("Expected options table, got {}", value.type_name())),
+            });
+        };
+
+        let default = Self::default();
+        let unit = |key: &str, default: f32| -> LuaResult<f32> {
+            let value = tab.get::<_, Option<f32>>(key)?.unwrap_or(default);
+            if (0.0..=1.0).contains(&value) {
+                Ok(value)
+            } else {
+                Err(LuaError::RuntimeError(format!(
+                    "Option '{key}' must be in the range [0, 1], got {value}"
+                )))
+            }
+        };
+
+        Ok(Self {
+            seed: tab.get("seed")?,
+            min_hue: unit("minHue", default.min_hue)?,
+            max_hue: unit("maxHue", default.max_hue)?,
+            min_saturation: unit("minSaturation", default.min_saturation)?,
+            min_value: unit("minValue", default.min_value)?,
+        })
+    }
+}
+
+fn ensure_unit_alpha(alpha: f32) -> LuaResult<f32> {
+    if (0.0..=1.0).contains(&alpha) {
+        Ok(alpha)
+    } else {
+        Err(LuaError::RuntimeError(format!(
+            "Alpha must be in the range [0, 1], got {alpha}"
+        )))
+    }
+}
+
+fn lerp_unit(min: f32, alpha: f32) -> f32 {
+    min + (1.0 - min) * alpha
+}
+
+/**
+    A blend mode for compositing two colors, one channel at a time.
+
+    The color that the blend method is called on is the base
+    layer, and the color passed to it is the blended layer.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color3BlendMode {
+    Multiply,
+    Screen,
+    Overlay,
+}
+
+impl Color3BlendMode {
+    fn blend(self, base: f32, blend: f32) -> f32 {
+        match self {
+            Self::Multiply => base * blend,
+            Self::Screen => 1.0 - (1.0 - base) * (1.0 - blend),
+            Self::Overlay => {
+                if base < 0.5 {
+                    2.0 * base * blend
+                } else {
+                    1.0 - 2.0 * (1.0 - base) * (1.0 - blend)
+                }
+            }
+        }
+    }
+}
+
+impl<'lua> FromLua<'lua> for Color3BlendMode {
+    fn from_lua(value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
+        let name = match &value {
+            LuaValue::String(s) => s.to_str()?.to_string(),
+            LuaValue::UserData(ud) if ud.is::<EnumItem>() => ud.borrow::<EnumItem>()?.name.clone(),
+            _ => {
+                return Err(LuaError::FromLuaConversionError {
+                    from: value.type_name(),
+                    to: "Color3BlendMode",
+                    message: Some(format!(
+                        "Expected string or EnumItem, got {}",
+                        value.type_name()
+                    )),
+                })
+            }
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "multiply" => Ok(Self::Multiply),
+            "screen" => Ok(Self::Screen),
+            "overlay" => Ok(Self::Overlay),
+            _ => Err(LuaError::RuntimeError(format!(
+                "Invalid blend mode '{name}' - expected Multiply, Screen or Overlay"
+            ))),
+        }
+    }
+}
+
 impl Default for Color3 {
     fn default() -> Self {
         Self {
@@ -55,6 +55,7 @@ impl LuaExportsTable<'_> for ColorSequence {
                     ],
                 })
             } else if let Ok(keypoints) = ArgsKeypoints::from_lua_multi(args, lua) {
+                ensure_sequence_keypoint_count("ColorSequence", keypoints.len())?;
                 Ok(ColorSequence {
                     keypoints: keypoints.iter().map(|k| **k).collect(),
                 })
@@ -66,8 +67,30 @@ impl LuaExportsTable<'_> for ColorSequence {
             }
         };
 
+        let color_sequence_from_values = |_, values: Vec<LuaUserDataRef<Color3>>| {
+            if values.len() < 2 {
+                return Err(LuaError::RuntimeError(format!(
+                    "ColorSequence.fromValues requires at least 2 values, got {}",
+                    values.len()
+                )));
+            }
+            ensure_sequence_keypoint_count("ColorSequence", values.len())?;
+            let last = (values.len() - 1) as f32;
+            Ok(ColorSequence {
+                keypoints: values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| ColorSequenceKeypoint {
+                        time: index as f32 / last,
+                        color: *value,
+                    })
+                    .collect(),
+            })
+        };
+
         TableBuilder::new(lua)?
             .with_function("new", color_sequence_new)?
+            .with_function("fromValues", color_sequence_from_values)?
             .build_readonly()
     }
 }
@@ -78,6 +101,24 @@ impl LuaUserData for ColorSequence {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        methods.add_method("reverse", |_, this, ()| {
+            // Color keypoints have no envelopes, so mirroring
+            // the times is all that is needed to reverse them
+            Ok(ColorSequence {
+                keypoints: this
+                    .keypoints
+                    .iter()
+                    .rev()
+                    .map(|keypoint| ColorSequenceKeypoint {
+                        time: 1.0 - keypoint.time,
+                        color: keypoint.color,
+                    })
+                    .collect(),
+            })
+        });
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
     }
@@ -42,6 +42,20 @@ impl LuaUserData for ColorSequenceKeypoint {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        methods.add_method("withTime", |_, this, time: f32| {
+            if !(0.0..=1.0).contains(&time) {
+                return Err(LuaError::RuntimeError(format!(
+                    "Keypoint time must be in the range [0, 1], got {time}"
+                )));
+            }
+            Ok(ColorSequenceKeypoint {
+                time,
+                color: this.color,
+            })
+        });
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
     }
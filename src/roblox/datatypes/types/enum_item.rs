@@ -59,6 +59,64 @@ impl EnumItem {
         let parent = Enum::from_name(enum_name)?;
         Self::from_enum_and_value(&parent, value)
     }
+
+    /**
+        Coerces a Lua value into an item of the enum with the given name.
+
+        In addition to an `EnumItem` of the same enum, this also accepts
+        the raw integer value or the name of an item, as a convenience.
+    */
+    pub(crate) fn coerce_from_lua(enum_name: &str, value: &LuaValue) -> LuaResult<Self> {
+        let parent = match Enum::from_name(enum_name) {
+            Some(parent) => parent,
+            None => {
+                return Err(LuaError::RuntimeError(format!(
+                    "Enum.{enum_name} does not exist"
+                )))
+            }
+        };
+        let item = match value {
+            LuaValue::UserData(ud) if ud.is::<EnumItem>() => {
+                let item = ud.borrow::<EnumItem>()?;
+                if item.parent != parent {
+                    return Err(LuaError::RuntimeError(format!(
+                        "Expected Enum.{enum_name}, got Enum.{}",
+                        item.parent.desc.name
+                    )));
+                }
+                Some(item.clone())
+            }
+            LuaValue::Integer(i) => u32::try_from(*i)
+                .ok()
+                .and_then(|v| Self::from_enum_and_value(&parent, v)),
+            LuaValue::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64 => {
+                Self::from_enum_and_value(&parent, *n as u32)
+            }
+            LuaValue::Number(_) => None,
+            LuaValue::String(s) => Self::from_enum_and_name(&parent, s.to_str()?),
+            _ => {
+                return Err(LuaError::FromLuaConversionError {
+                    from: value.type_name(),
+                    to: "EnumItem",
+                    message: Some(format!(
+                        "Expected Enum.{enum_name}, number or string, got {}",
+                        value.type_name()
+                    )),
+                })
+            }
+        };
+        item.ok_or_else(|| {
+            let given = match value {
+                LuaValue::String(s) => format!("'{}'", s.to_string_lossy()),
+                LuaValue::Integer(i) => i.to_string(),
+                LuaValue::Number(n) => n.to_string(),
+                _ => value.type_name().to_string(),
+            };
+            LuaError::RuntimeError(format!(
+                "Enum.{enum_name} does not contain an item matching {given}"
+            ))
+        })
+    }
 }
 
 impl LuaUserData for EnumItem {
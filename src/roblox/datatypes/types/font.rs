@@ -42,20 +42,14 @@ impl LuaExportsTable<'_> for Font {
     const EXPORT_NAME: &'static str = "Font";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        let font_from_enum = |_, value: LuaUserDataRef<EnumItem>| {
-            if value.parent.desc.name == "Font" {
-                match Font::from_enum_item(&value) {
-                    Some(props) => Ok(props),
-                    None => Err(LuaError::RuntimeError(format!(
-                        "Found unknown Font '{}'",
-                        value.name
-                    ))),
-                }
-            } else {
-                Err(LuaError::RuntimeError(format!(
-                    "Expected argument #1 to be a Font, got {}",
-                    value.parent.desc.name
-                )))
+        let font_from_enum = |_, value: LuaValue| {
+            let value = EnumItem::coerce_from_lua("Font", &value)?;
+            match Font::from_enum_item(&value) {
+                Some(props) => Ok(props),
+                None => Err(LuaError::RuntimeError(format!(
+                    "Found unknown Font '{}'",
+                    value.name
+                ))),
             }
         };
 
@@ -282,29 +276,14 @@ impl std::fmt::Display for FontWeight {
 
 impl<'lua> FromLua<'lua> for FontWeight {
     fn from_lua(lua_value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
-        let mut message = None;
-        if let LuaValue::UserData(ud) = &lua_value {
-            let value = ud.borrow::<EnumItem>()?;
-            if value.parent.desc.name == "FontWeight" {
-                if let Ok(value) = FontWeight::from_str(&value.name) {
-                    return Ok(value);
-                } else {
-                    message = Some(format!(
-                        "Found unknown Enum.FontWeight value '{}'",
-                        value.name
-                    ));
-                }
-            } else {
-                message = Some(format!(
-                    "Expected Enum.FontWeight, got Enum.{}",
-                    value.parent.desc.name
-                ));
-            }
-        }
-        Err(LuaError::FromLuaConversionError {
+        let value = EnumItem::coerce_from_lua("FontWeight", &lua_value)?;
+        FontWeight::from_str(&value.name).map_err(|_| LuaError::FromLuaConversionError {
             from: lua_value.type_name(),
             to: "Enum.FontWeight",
-            message,
+            message: Some(format!(
+                "Found unknown Enum.FontWeight value '{}'",
+                value.name
+            )),
         })
     }
 }
@@ -377,29 +356,14 @@ impl std::fmt::Display for FontStyle {
 
 impl<'lua> FromLua<'lua> for FontStyle {
     fn from_lua(lua_value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
-        let mut message = None;
-        if let LuaValue::UserData(ud) = &lua_value {
-            let value = ud.borrow::<EnumItem>()?;
-            if value.parent.desc.name == "FontStyle" {
-                if let Ok(value) = FontStyle::from_str(&value.name) {
-                    return Ok(value);
-                } else {
-                    message = Some(format!(
-                        "Found unknown Enum.FontStyle value '{}'",
-                        value.name
-                    ));
-                }
-            } else {
-                message = Some(format!(
-                    "Expected Enum.FontStyle, got Enum.{}",
-                    value.parent.desc.name
-                ));
-            }
-        }
-        Err(LuaError::FromLuaConversionError {
+        let value = EnumItem::coerce_from_lua("FontStyle", &lua_value)?;
+        FontStyle::from_str(&value.name).map_err(|_| LuaError::FromLuaConversionError {
             from: lua_value.type_name(),
             to: "Enum.FontStyle",
-            message,
+            message: Some(format!(
+                "Found unknown Enum.FontStyle value '{}'",
+                value.name
+            )),
         })
     }
 }
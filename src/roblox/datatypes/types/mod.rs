@@ -49,3 +49,186 @@ pub use vector2::Vector2;
 pub use vector2int16::Vector2int16;
 pub use vector3::Vector3;
 pub use vector3int16::Vector3int16;
+
+#[cfg(test)]
+mod tests {
+    use mlua::prelude::*;
+
+    /**
+        Metamethods that every datatype must implement.
+    */
+    const BASE: &[LuaMetaMethod] = &[LuaMetaMethod::Eq, LuaMetaMethod::ToString];
+
+    /**
+        Metamethods that every datatype in the "math" family must implement,
+        such as vectors, colors, and other types that can be added together.
+    */
+    const MATH: &[LuaMetaMethod] = &[
+        LuaMetaMethod::Eq,
+        LuaMetaMethod::ToString,
+        LuaMetaMethod::Unm,
+        LuaMetaMethod::Add,
+        LuaMetaMethod::Sub,
+    ];
+
+    /**
+        Metamethods for math types that may also be scaled by numbers.
+    */
+    const MATH_SCALAR: &[LuaMetaMethod] = &[
+        LuaMetaMethod::Eq,
+        LuaMetaMethod::ToString,
+        LuaMetaMethod::Unm,
+        LuaMetaMethod::Add,
+        LuaMetaMethod::Sub,
+        LuaMetaMethod::Mul,
+        LuaMetaMethod::Div,
+    ];
+
+    /**
+        Every exported datatype, with a Luau expression creating a value
+        of it, and the metamethods that value is expected to implement.
+
+        Any new datatype should be added here to make sure it gets registered properly.
+    */
+    const DATATYPES: &[(&str, &str, &[LuaMetaMethod])] = &[
+        ("Axes", "Axes.new(Enum.Axis.X)", BASE),
+        ("BrickColor", "BrickColor.new(1)", BASE),
+        (
+            "CFrame",
+            "CFrame.new()",
+            &[
+                LuaMetaMethod::Eq,
+                LuaMetaMethod::ToString,
+                LuaMetaMethod::Add,
+                LuaMetaMethod::Sub,
+                LuaMetaMethod::Mul,
+            ],
+        ),
+        ("Color3", "Color3.new()", MATH_SCALAR),
+        ("ColorSequence", "ColorSequence.new(Color3.new())", BASE),
+        (
+            "ColorSequenceKeypoint",
+            "ColorSequenceKeypoint.new(0, Color3.new())",
+            BASE,
+        ),
+        ("Enum", "Enum.Axis", BASE),
+        ("EnumItem", "Enum.Axis.X", BASE),
+        ("Enums", "Enum", BASE),
+        ("Faces", "Faces.new(Enum.NormalId.Top)", BASE),
+        ("Font", "Font.new(\"\")", BASE),
+        ("NumberRange", "NumberRange.new(0)", BASE),
+        ("NumberSequence", "NumberSequence.new(0)", BASE),
+        (
+            "NumberSequenceKeypoint",
+            "NumberSequenceKeypoint.new(0, 0)",
+            BASE,
+        ),
+        (
+            "PhysicalProperties",
+            "PhysicalProperties.new(0, 0, 0)",
+            BASE,
+        ),
+        ("Ray", "Ray.new(Vector3.zero, Vector3.one)", BASE),
+        ("Rect", "Rect.new()", MATH),
+        ("Region3", "Region3.new(Vector3.zero, Vector3.one)", BASE),
+        (
+            "Region3int16",
+            "Region3int16.new(Vector3int16.new(), Vector3int16.new())",
+            BASE,
+        ),
+        ("UDim", "UDim.new()", MATH),
+        ("UDim2", "UDim2.new()", MATH),
+        ("Vector2", "Vector2.new()", MATH_SCALAR),
+        ("Vector2int16", "Vector2int16.new()", MATH_SCALAR),
+        ("Vector3", "Vector3.new()", MATH_SCALAR),
+        ("Vector3int16", "Vector3int16.new()", MATH_SCALAR),
+    ];
+
+    /**
+        Creates an environment table containing everything in the `roblox` library.
+    */
+    fn create_roblox_env(lua: &Lua) -> LuaResult<LuaTable<'_>> {
+        let env = lua.create_table()?;
+        for pair in crate::roblox::module(lua)?.pairs::<LuaValue, LuaValue>() {
+            let (key, value) = pair?;
+            env.raw_set(key, value)?;
+        }
+        Ok(env)
+    }
+
+    /**
+        Evaluates the given Luau expression with the `roblox` library available
+        in its environment, and returns the resulting metatable for the userdata.
+    */
+    fn create_value_metatable<'lua>(
+        lua: &'lua Lua,
+        expr: &str,
+    ) -> LuaResult<LuaUserDataMetatable<'lua>> {
+        let env = create_roblox_env(lua)?;
+        let value = lua
+            .load(format!("return {expr}"))
+            .set_environment(env)
+            .eval::<LuaAnyUserData>()?;
+        value.get_metatable()
+    }
+
+    #[test]
+    fn datatypes_register_metamethods() {
+        let lua = Lua::new();
+        for (name, expr, expected) in DATATYPES {
+            let metatable = create_value_metatable(&lua, expr)
+                .unwrap_or_else(|e| panic!("failed to create {name} using `{expr}`: {e}"));
+            for method in expected.iter() {
+                assert!(
+                    metatable.contains(method.clone()).unwrap(),
+                    "{name} is missing the {method} metamethod"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn datatypes_are_all_covered() {
+        let lua = Lua::new();
+        for pair in crate::roblox::module(&lua)
+            .unwrap()
+            .pairs::<String, LuaValue>()
+        {
+            let (name, _) = pair.unwrap();
+            if name == "Instance" {
+                continue;
+            }
+            assert!(
+                DATATYPES
+                    .iter()
+                    .any(|(n, _, _)| *n == name || name == "Enum"),
+                "exported datatype {name} is missing from the metamethod registration tests"
+            );
+        }
+    }
+
+    #[test]
+    fn datatypes_tostring_from_luau() {
+        let lua = Lua::new();
+        for (name, expr, _) in DATATYPES {
+            let env = create_roblox_env(&lua).unwrap();
+            env.raw_set(
+                "tostring",
+                lua.globals().get::<_, LuaFunction>("tostring").unwrap(),
+            )
+            .unwrap();
+            let string = lua
+                .load(format!("return tostring({expr})"))
+                .set_environment(env)
+                .eval::<String>()
+                .unwrap_or_else(|e| panic!("failed to call tostring on {name}: {e}"));
+
+            // Userdata without a working __tostring metamethod
+            // are formatted as their type name and address instead
+            assert!(
+                !string.is_empty() && !string.contains(": 0x"),
+                "tostring on {name} returned {string:?}"
+            );
+        }
+    }
+}
@@ -22,7 +22,9 @@ impl LuaExportsTable<'_> for NumberRange {
     const EXPORT_NAME: &'static str = "NumberRange";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        let number_range_new = |_, (min, max): (f32, Option<f32>)| {
+        let number_range_new = |lua, args: LuaMultiValue| {
+            let (min, max): (f32, Option<f32>) =
+                constructor_args(lua, "NumberRange.new", 1..=2, args)?;
             Ok(match max {
                 Some(max) => NumberRange {
                     min: min.min(max),
@@ -45,6 +47,9 @@ impl LuaUserData for NumberRange {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
     }
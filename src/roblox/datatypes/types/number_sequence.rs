@@ -59,6 +59,7 @@ impl LuaExportsTable<'_> for NumberSequence {
                     ],
                 })
             } else if let Ok(keypoints) = ArgsKeypoints::from_lua_multi(args, lua) {
+                ensure_sequence_keypoint_count("NumberSequence", keypoints.len())?;
                 Ok(NumberSequence {
                     keypoints: keypoints.iter().map(|k| **k).collect(),
                 })
@@ -70,8 +71,31 @@ impl LuaExportsTable<'_> for NumberSequence {
             }
         };
 
+        let number_sequence_from_values = |_, values: Vec<f32>| {
+            if values.len() < 2 {
+                return Err(LuaError::RuntimeError(format!(
+                    "NumberSequence.fromValues requires at least 2 values, got {}",
+                    values.len()
+                )));
+            }
+            ensure_sequence_keypoint_count("NumberSequence", values.len())?;
+            let last = (values.len() - 1) as f32;
+            Ok(NumberSequence {
+                keypoints: values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| NumberSequenceKeypoint {
+                        time: index as f32 / last,
+                        value,
+                        envelope: 0.0,
+                    })
+                    .collect(),
+            })
+        };
+
         TableBuilder::new(lua)?
             .with_function("new", number_sequence_new)?
+            .with_function("fromValues", number_sequence_from_values)?
             .build_readonly()
     }
 }
@@ -82,6 +106,9 @@ impl LuaUserData for NumberSequence {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
     }
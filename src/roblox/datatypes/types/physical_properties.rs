@@ -34,31 +34,32 @@ impl PhysicalProperties {
                 elasticity_weight: props.5,
             })
     }
+
+    /**
+        Gets the default physical properties for a material, given as an
+        `Enum.Material` item, or a value that can be coerced into one.
+    */
+    pub(crate) fn from_material_lua(value: &LuaValue) -> LuaResult<PhysicalProperties> {
+        let material = EnumItem::coerce_from_lua("Material", value)?;
+        match PhysicalProperties::from_material(&material) {
+            Some(props) => Ok(props),
+            None => Err(LuaError::RuntimeError(format!(
+                "Found unknown Material '{}'",
+                material.name
+            ))),
+        }
+    }
 }
 
 impl LuaExportsTable<'_> for PhysicalProperties {
     const EXPORT_NAME: &'static str = "PhysicalProperties";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        type ArgsMaterial<'lua> = LuaUserDataRef<'lua, EnumItem>;
         type ArgsNumbers = (f32, f32, f32, Option<f32>, Option<f32>);
 
         let physical_properties_new = |lua, args: LuaMultiValue| {
-            if let Ok(value) = ArgsMaterial::from_lua_multi(args.clone(), lua) {
-                if value.parent.desc.name == "Material" {
-                    match PhysicalProperties::from_material(&value) {
-                        Some(props) => Ok(props),
-                        None => Err(LuaError::RuntimeError(format!(
-                            "Found unknown Material '{}'",
-                            value.name
-                        ))),
-                    }
-                } else {
-                    Err(LuaError::RuntimeError(format!(
-                        "Expected argument #1 to be a Material, got {}",
-                        value.parent.desc.name
-                    )))
-                }
+            if args.len() == 1 {
+                PhysicalProperties::from_material_lua(&args[0])
             } else if let Ok((density, friction, elasticity, friction_weight, elasticity_weight)) =
                 ArgsNumbers::from_lua_multi(args, lua)
             {
@@ -93,6 +94,9 @@ impl LuaUserData for PhysicalProperties {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
     }
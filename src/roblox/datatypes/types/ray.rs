@@ -34,13 +34,14 @@ impl LuaExportsTable<'_> for Ray {
     const EXPORT_NAME: &'static str = "Ray";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        let ray_new =
-            |_, (origin, direction): (LuaUserDataRef<Vector3>, LuaUserDataRef<Vector3>)| {
-                Ok(Ray {
-                    origin: origin.0,
-                    direction: direction.0,
-                })
-            };
+        let ray_new = |lua, args: LuaMultiValue| {
+            let (origin, direction): (LuaUserDataRef<Vector3>, LuaUserDataRef<Vector3>) =
+                constructor_args(lua, "Ray.new", 2..=2, args)?;
+            Ok(Ray {
+                origin: origin.0,
+                direction: direction.0,
+            })
+        };
 
         TableBuilder::new(lua)?
             .with_function("new", ray_new)?
@@ -69,6 +70,7 @@ impl LuaUserData for Ray {
             let closest = this.closest_point(to.0);
             Ok((closest - to.0).length())
         });
+        methods.add_method("equals", approx::userdata_impl_equals);
         // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
@@ -22,12 +22,36 @@ pub struct Rect {
 }
 
 impl Rect {
-    fn new(lhs: Vec2, rhs: Vec2) -> Self {
+    pub(crate) fn new(lhs: Vec2, rhs: Vec2) -> Self {
         Self {
             min: lhs.min(rhs),
             max: lhs.max(rhs),
         }
     }
+
+    /**
+        Checks if the given point is within this rect, edges included.
+    */
+    pub(crate) fn contains_point(&self, point: Vec2) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /**
+        Grows this rect by the given amount on all sides, or shrinks it if the amount is negative.
+
+        Shrinking is clamped so that `min` never passes `max`, and any axis that would
+        become negative in size instead collapses to zero size around its center.
+    */
+    fn grow(&self, amount: Vec2) -> Self {
+        let min = self.min - amount;
+        let max = self.max + amount;
+        let center = (self.min + self.max) / 2.0;
+        let collapsed = min.cmpgt(max);
+        Self {
+            min: Vec2::select(collapsed, center, min),
+            max: Vec2::select(collapsed, center, max),
+        }
+    }
 }
 
 impl LuaExportsTable<'_> for Rect {
@@ -73,6 +97,19 @@ impl LuaUserData for Rect {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method(
+            "containsPoint",
+            |_, this, point: LuaUserDataRef<Vector2>| Ok(this.contains_point(point.0)),
+        );
+        methods.add_method("equals", approx::userdata_impl_equals);
+        methods.add_method("expand", |_, this, amount: LuaValue| {
+            Ok(this.grow(amount_from_lua(amount)?))
+        });
+        methods.add_method("inset", |_, this, amount: LuaValue| {
+            Ok(this.grow(-amount_from_lua(amount)?))
+        });
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
         methods.add_meta_method(LuaMetaMethod::Unm, userdata_impl_unm);
@@ -81,6 +118,26 @@ impl LuaUserData for Rect {
     }
 }
 
+/**
+    Gets the amount to expand or inset a rect by, which may either be
+    a single number for all sides, or a `Vector2` for each axis.
+*/
+fn amount_from_lua(value: LuaValue) -> LuaResult<Vec2> {
+    match &value {
+        LuaValue::Integer(i) => Ok(Vec2::splat(*i as f32)),
+        LuaValue::Number(n) => Ok(Vec2::splat(*n as f32)),
+        LuaValue::UserData(ud) if ud.is::<Vector2>() => Ok(ud.borrow::<Vector2>()?.0),
+        _ => Err(LuaError::FromLuaConversionError {
+            from: value.type_name(),
+            to: "Vector2",
+            message: Some(format!(
+                "Expected number or Vector2, got {}",
+                value.type_name()
+            )),
+        }),
+    }
+}
+
 impl fmt::Display for Rect {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}, {}", self.min, self.max)
@@ -110,10 +167,7 @@ impl ops::Sub for Rect {
 
 impl From<DomRect> for Rect {
     fn from(v: DomRect) -> Self {
-        Rect {
-            min: Vec2::new(v.min.x, v.min.y),
-            max: Vec2::new(v.max.x, v.max.y),
-        }
+        Rect::new(Vec2::new(v.min.x, v.min.y), Vec2::new(v.max.x, v.max.y))
     }
 }
 
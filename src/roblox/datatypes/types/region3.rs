@@ -24,7 +24,9 @@ impl LuaExportsTable<'_> for Region3 {
     const EXPORT_NAME: &'static str = "Region3";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        let region3_new = |_, (min, max): (LuaUserDataRef<Vector3>, LuaUserDataRef<Vector3>)| {
+        let region3_new = |lua, args: LuaMultiValue| {
+            let (min, max): (LuaUserDataRef<Vector3>, LuaUserDataRef<Vector3>) =
+                constructor_args(lua, "Region3.new", 2..=2, args)?;
             Ok(Region3 {
                 min: min.0,
                 max: max.0,
@@ -47,6 +49,7 @@ impl LuaUserData for Region3 {
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
         methods.add_method("ExpandToGrid", |_, this, resolution: f32| {
             Ok(Region3 {
                 min: (this.min / resolution).floor() * resolution,
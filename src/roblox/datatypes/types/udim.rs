@@ -1,6 +1,7 @@
 use core::fmt;
 use std::ops;
 
+use glam::Vec2;
 use mlua::prelude::*;
 use rbx_dom_weak::types::UDim as DomUDim;
 
@@ -23,6 +24,16 @@ impl UDim {
     pub(super) fn new(scale: f32, offset: i32) -> Self {
         Self { scale, offset }
     }
+
+    pub(super) fn lerp(&self, goal: &Self, alpha: f32) -> Self {
+        let this = Vec2::new(self.scale, self.offset as f32);
+        let goal = Vec2::new(goal.scale, goal.offset as f32);
+        let lerped = this.lerp(goal, alpha);
+        Self {
+            scale: lerped.x,
+            offset: lerped.y.clamp(i32::MIN as f32, i32::MAX as f32).round() as i32,
+        }
+    }
 }
 
 impl LuaExportsTable<'_> for UDim {
@@ -49,6 +60,13 @@ impl LuaUserData for UDim {
     }
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
+        methods.add_method(
+            "Lerp",
+            |_, this, (goal, alpha): (LuaUserDataRef<UDim>, f32)| Ok(this.lerp(&goal, alpha)),
+        );
+        // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
         methods.add_meta_method(LuaMetaMethod::Unm, userdata_impl_unm);
@@ -20,6 +20,19 @@ pub struct UDim2 {
     pub(crate) y: UDim,
 }
 
+impl UDim2 {
+    /**
+        Resolves this UDim2 into an absolute size or position, in pixels,
+        by scaling against the given parent size and adding the offsets.
+    */
+    pub(crate) fn resolve(&self, parent_size: Vec2) -> Vec2 {
+        Vec2::new(
+            self.x.scale * parent_size.x + self.x.offset as f32,
+            self.y.scale * parent_size.y + self.y.offset as f32,
+        )
+    }
+}
+
 impl LuaExportsTable<'_> for UDim2 {
     const EXPORT_NAME: &'static str = "UDim2";
 
@@ -80,27 +93,13 @@ impl LuaUserData for UDim2 {
 
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         // Methods
+        methods.add_method("equals", approx::userdata_impl_equals);
         methods.add_method(
             "Lerp",
             |_, this, (goal, alpha): (LuaUserDataRef<UDim2>, f32)| {
-                let this_x = Vec2::new(this.x.scale, this.x.offset as f32);
-                let goal_x = Vec2::new(goal.x.scale, goal.x.offset as f32);
-
-                let this_y = Vec2::new(this.y.scale, this.y.offset as f32);
-                let goal_y = Vec2::new(goal.y.scale, goal.y.offset as f32);
-
-                let x = this_x.lerp(goal_x, alpha);
-                let y = this_y.lerp(goal_y, alpha);
-
                 Ok(UDim2 {
-                    x: UDim {
-                        scale: x.x,
-                        offset: x.y.clamp(i32::MIN as f32, i32::MAX as f32).round() as i32,
-                    },
-                    y: UDim {
-                        scale: y.x,
-                        offset: y.y.clamp(i32::MIN as f32, i32::MAX as f32).round() as i32,
-                    },
+                    x: this.x.lerp(&goal.x, alpha),
+                    y: this.y.lerp(&goal.y, alpha),
                 })
             },
         );
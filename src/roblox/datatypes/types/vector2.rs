@@ -4,10 +4,14 @@ use std::ops;
 use glam::{Vec2, Vec3};
 use mlua::prelude::*;
 use rbx_dom_weak::types::Vector2 as DomVector2;
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{lune::util::TableBuilder, roblox::exports::LuaExportsTable};
+use crate::{
+    lune::util::TableBuilder,
+    roblox::{datatypes::util::deserialize_vector_components, exports::LuaExportsTable},
+};
 
-use super::super::*;
+use super::{super::*, Vector3};
 
 /**
     An implementation of the [Vector2](https://create.roblox.com/docs/reference/engine/datatypes/Vector2)
@@ -30,12 +34,15 @@ impl LuaExportsTable<'_> for Vector2 {
             }))
         };
 
+        let vector2_from_angle = |_, angle: f32| Ok(Vector2(Vec2::from_angle(angle)));
+
         TableBuilder::new(lua)?
             .with_value("xAxis", Vector2(Vec2::X))?
             .with_value("yAxis", Vector2(Vec2::Y))?
             .with_value("zero", Vector2(Vec2::ZERO))?
             .with_value("one", Vector2(Vec2::ONE))?
             .with_function("new", vector2_new)?
+            .with_function("fromAngle", vector2_from_angle)?
             .build_readonly()
     }
 }
@@ -58,6 +65,7 @@ impl LuaUserData for Vector2 {
         methods.add_method("Dot", |_, this, rhs: LuaUserDataRef<Vector2>| {
             Ok(this.0.dot(rhs.0))
         });
+        methods.add_method("equals", approx::userdata_impl_equals);
         methods.add_method(
             "Lerp",
             |_, this, (rhs, alpha): (LuaUserDataRef<Vector2>, f32)| {
@@ -70,6 +78,16 @@ impl LuaUserData for Vector2 {
         methods.add_method("Min", |_, this, rhs: LuaUserDataRef<Vector2>| {
             Ok(Vector2(this.0.min(rhs.0)))
         });
+        methods.add_method("toAngle", |_, this, ()| Ok(this.0.y.atan2(this.0.x)));
+        methods.add_method("toVector3XZ", |_, this, y: Option<f32>| {
+            Ok(Vector3(Vec3::new(
+                this.0.x,
+                y.unwrap_or_default(),
+                this.0.y,
+            )))
+        });
+        methods.add_method("withX", |_, this, x: f32| Ok(Vector2(this.0.with_x(x))));
+        methods.add_method("withY", |_, this, y: f32| Ok(Vector2(this.0.with_y(y))));
         // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
@@ -136,6 +154,22 @@ impl ops::Div<f32> for Vector2 {
     }
 }
 
+impl Serialize for Vector2 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Vector2", 2)?;
+        state.serialize_field("x", &self.0.x)?;
+        state.serialize_field("y", &self.0.y)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Vector2 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y] = deserialize_vector_components(deserializer, "Vector2", &["x", "y"])?;
+        Ok(Vector2(Vec2::new(x, y)))
+    }
+}
+
 impl From<DomVector2> for Vector2 {
     fn from(v: DomVector2) -> Self {
         Vector2(Vec2 { x: v.x, y: v.y })
@@ -19,6 +19,19 @@ use super::super::*;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector2int16(pub IVec2);
 
+impl Vector2int16 {
+    /**
+        Brings the result of an arithmetic operation back into
+        the 16-bit integer range, using the given overflow behavior.
+    */
+    fn overflowing(self, mode: Int16Overflow) -> Self {
+        Self(IVec2 {
+            x: mode.apply(self.0.x),
+            y: mode.apply(self.0.y),
+        })
+    }
+}
+
 impl LuaExportsTable<'_> for Vector2int16 {
     const EXPORT_NAME: &'static str = "Vector2int16";
 
@@ -45,11 +58,21 @@ impl LuaUserData for Vector2int16 {
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
-        methods.add_meta_method(LuaMetaMethod::Unm, userdata_impl_unm);
-        methods.add_meta_method(LuaMetaMethod::Add, userdata_impl_add);
-        methods.add_meta_method(LuaMetaMethod::Sub, userdata_impl_sub);
-        methods.add_meta_method(LuaMetaMethod::Mul, userdata_impl_mul_i32);
-        methods.add_meta_method(LuaMetaMethod::Div, userdata_impl_div_i32);
+        methods.add_meta_method(LuaMetaMethod::Unm, |lua, this, ()| {
+            Ok(userdata_impl_unm(lua, this, ())?.overflowing(Int16Overflow::get(lua)))
+        });
+        methods.add_meta_method(LuaMetaMethod::Add, |lua, this, rhs| {
+            Ok(userdata_impl_add(lua, this, rhs)?.overflowing(Int16Overflow::get(lua)))
+        });
+        methods.add_meta_method(LuaMetaMethod::Sub, |lua, this, rhs| {
+            Ok(userdata_impl_sub(lua, this, rhs)?.overflowing(Int16Overflow::get(lua)))
+        });
+        methods.add_meta_method(LuaMetaMethod::Mul, |lua, this, rhs| {
+            Ok(userdata_impl_mul_i32(lua, this, rhs)?.overflowing(Int16Overflow::get(lua)))
+        });
+        methods.add_meta_method(LuaMetaMethod::Div, |lua, this, rhs| {
+            Ok(userdata_impl_div_i32(lua, this, rhs)?.overflowing(Int16Overflow::get(lua)))
+        });
     }
 }
 
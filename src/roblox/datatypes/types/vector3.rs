@@ -1,13 +1,20 @@
 use core::fmt;
-use std::ops;
+use std::{f32::consts::PI, ops};
 
-use glam::Vec3;
+use glam::{Quat, Vec3, Vec4};
 use mlua::prelude::*;
 use rbx_dom_weak::types::Vector3 as DomVector3;
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     lune::util::TableBuilder,
-    roblox::{datatypes::util::round_float_decimal, exports::LuaExportsTable},
+    roblox::{
+        datatypes::util::{deserialize_vector_components, round_float_decimal},
+        exports::LuaExportsTable,
+        shared::buffer::{
+            ensure_buffer, ensure_buffer_vector3s, read_vector3s, BUFFER_VECTOR3_SIZE,
+        },
+    },
 };
 
 use super::{super::*, EnumItem};
@@ -25,53 +32,80 @@ use super::{super::*, EnumItem};
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector3(pub Vec3);
 
+impl Vector3 {
+    /**
+        Gets the unit vector pointing along the given `Enum.Axis` item.
+    */
+    pub(crate) fn from_axis(axis: &EnumItem) -> LuaResult<Self> {
+        if axis.parent.desc.name == "Axis" {
+            Ok(match axis.name.as_str() {
+                "X" => Vector3(Vec3::X),
+                "Y" => Vector3(Vec3::Y),
+                "Z" => Vector3(Vec3::Z),
+                name => {
+                    return Err(LuaError::RuntimeError(format!(
+                        "Axis '{}' is not known",
+                        name
+                    )))
+                }
+            })
+        } else {
+            Err(LuaError::RuntimeError(format!(
+                "EnumItem must be a Axis, got {}",
+                axis.parent.desc.name
+            )))
+        }
+    }
+
+    /**
+        Gets the unit vector pointing out of the face for the given `Enum.NormalId` item.
+    */
+    pub(crate) fn from_normal_id(normal_id: &EnumItem) -> LuaResult<Self> {
+        if normal_id.parent.desc.name == "NormalId" {
+            NORMAL_ID_DIRECTIONS
+                .iter()
+                .find(|(name, _)| *name == normal_id.name)
+                .map(|(_, direction)| Vector3(*direction))
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!("NormalId '{}' is not known", normal_id.name))
+                })
+        } else {
+            Err(LuaError::RuntimeError(format!(
+                "EnumItem must be a NormalId, got {}",
+                normal_id.parent.desc.name
+            )))
+        }
+    }
+}
+
 impl LuaExportsTable<'_> for Vector3 {
     const EXPORT_NAME: &'static str = "Vector3";
 
     fn create_exports_table(lua: &Lua) -> LuaResult<LuaTable> {
-        let vector3_from_axis = |_, normal_id: LuaUserDataRef<EnumItem>| {
-            if normal_id.parent.desc.name == "Axis" {
-                Ok(match normal_id.name.as_str() {
-                    "X" => Vector3(Vec3::X),
-                    "Y" => Vector3(Vec3::Y),
-                    "Z" => Vector3(Vec3::Z),
-                    name => {
-                        return Err(LuaError::RuntimeError(format!(
-                            "Axis '{}' is not known",
-                            name
-                        )))
-                    }
-                })
-            } else {
-                Err(LuaError::RuntimeError(format!(
-                    "EnumItem must be a Axis, got {}",
-                    normal_id.parent.desc.name
-                )))
-            }
+        let vector3_from_axis = |_, axis: LuaUserDataRef<EnumItem>| Vector3::from_axis(&axis);
+
+        let vector3_from_normal_id =
+            |_, normal_id: LuaUserDataRef<EnumItem>| Vector3::from_normal_id(&normal_id);
+
+        let vector3_from_buffer = |lua, (buffer, offset): (LuaValue, Option<usize>)| {
+            let offset = offset.unwrap_or_default();
+            let (buffer_lib, buffer) = ensure_buffer_bounds(lua, buffer, offset)?;
+            let read_f32 = buffer_lib.get::<_, LuaFunction>("readf32")?;
+            Ok(Vector3(Vec3 {
+                x: read_f32.call((buffer.clone(), offset))?,
+                y: read_f32.call((buffer.clone(), offset + 4))?,
+                z: read_f32.call((buffer, offset + 8))?,
+            }))
         };
 
-        let vector3_from_normal_id = |_, normal_id: LuaUserDataRef<EnumItem>| {
-            if normal_id.parent.desc.name == "NormalId" {
-                Ok(match normal_id.name.as_str() {
-                    "Left" => Vector3(Vec3::X),
-                    "Top" => Vector3(Vec3::Y),
-                    "Front" => Vector3(-Vec3::Z),
-                    "Right" => Vector3(-Vec3::X),
-                    "Bottom" => Vector3(-Vec3::Y),
-                    "Back" => Vector3(Vec3::Z),
-                    name => {
-                        return Err(LuaError::RuntimeError(format!(
-                            "NormalId '{}' is not known",
-                            name
-                        )))
-                    }
-                })
-            } else {
-                Err(LuaError::RuntimeError(format!(
-                    "EnumItem must be a NormalId, got {}",
-                    normal_id.parent.desc.name
-                )))
-            }
+        let vector3_magnitudes_from_buffer = |lua: &Lua, buffer: LuaValue| {
+            let (_, bytes) = ensure_buffer_vector3s(lua, buffer)?;
+            Ok(batch_magnitudes(bytes.as_bytes()))
+        };
+
+        let vector3_from_table = |lua: &Lua, table: LuaTable| {
+            // Uses the same shapes as deserializing, see the Deserialize impl below
+            lua.from_value::<Vector3>(LuaValue::Table(table))
         };
 
         let vector3_new = |_, (x, y, z): (Option<f32>, Option<f32>, Option<f32>)| {
@@ -89,7 +123,10 @@ impl LuaExportsTable<'_> for Vector3 {
             .with_value("zero", Vector3(Vec3::ZERO))?
             .with_value("one", Vector3(Vec3::ONE))?
             .with_function("fromAxis", vector3_from_axis)?
+            .with_function("fromBuffer", vector3_from_buffer)?
             .with_function("fromNormalId", vector3_from_normal_id)?
+            .with_function("fromTable", vector3_from_table)?
+            .with_function("magnitudesFromBuffer", vector3_magnitudes_from_buffer)?
             .with_function("new", vector3_new)?
             .build_readonly()
     }
@@ -109,12 +146,30 @@ impl LuaUserData for Vector3 {
         methods.add_method("Angle", |_, this, rhs: LuaUserDataRef<Vector3>| {
             Ok(this.0.angle_between(rhs.0))
         });
+        methods.add_method("chebyshev", |_, this, rhs: LuaUserDataRef<Vector3>| {
+            Ok((this.0 - rhs.0).abs().max_element())
+        });
         methods.add_method("Cross", |_, this, rhs: LuaUserDataRef<Vector3>| {
             Ok(Vector3(this.0.cross(rhs.0)))
         });
         methods.add_method("Dot", |_, this, rhs: LuaUserDataRef<Vector3>| {
             Ok(this.0.dot(rhs.0))
         });
+        methods.add_method("dominantAxis", |_, this, ()| {
+            // Ties are broken in favor of the earlier axis, X > Y > Z
+            let abs = this.0.abs();
+            let name = if abs.x >= abs.y && abs.x >= abs.z {
+                "X"
+            } else if abs.y >= abs.z {
+                "Y"
+            } else {
+                "Z"
+            };
+            EnumItem::from_enum_name_and_name("Axis", name).ok_or_else(|| {
+                LuaError::RuntimeError(format!("Enum.Axis does not contain an item named {name}"))
+            })
+        });
+        methods.add_method("equals", approx::userdata_impl_equals);
         methods.add_method(
             "FuzzyEq",
             |_, this, (rhs, epsilon): (LuaUserDataRef<Vector3>, f32)| {
@@ -124,18 +179,150 @@ impl LuaUserData for Vector3 {
                 Ok(eq_x && eq_y && eq_z)
             },
         );
+        methods.add_method("isAxisAligned", |_, this, epsilon: Option<f32>| {
+            let epsilon = epsilon.unwrap_or(AXIS_ALIGNED_EPSILON);
+            let direction = this.0.normalize_or_zero();
+            if direction == Vec3::ZERO {
+                return Ok(false);
+            }
+            let off_axis = direction.abs().cmpgt(Vec3::splat(epsilon)).bitmask();
+            Ok(off_axis.count_ones() == 1)
+        });
         methods.add_method(
             "Lerp",
             |_, this, (rhs, alpha): (LuaUserDataRef<Vector3>, f32)| {
                 Ok(Vector3(this.0.lerp(rhs.0, alpha)))
             },
         );
+        methods.add_method(
+            "lerpClamped",
+            |_, this, (rhs, alpha): (LuaUserDataRef<Vector3>, f32)| {
+                Ok(Vector3(this.0.lerp(rhs.0, alpha.clamp(0.0, 1.0))))
+            },
+        );
+        methods.add_method("manhattan", |_, this, rhs: LuaUserDataRef<Vector3>| {
+            Ok((this.0 - rhs.0).abs().element_sum())
+        });
         methods.add_method("Max", |_, this, rhs: LuaUserDataRef<Vector3>| {
             Ok(Vector3(this.0.max(rhs.0)))
         });
         methods.add_method("Min", |_, this, rhs: LuaUserDataRef<Vector3>| {
             Ok(Vector3(this.0.min(rhs.0)))
         });
+        methods.add_method("pow", |_, this, exponent: LuaValue| {
+            // Negative bases give real results only for integer exponents, such
+            // as (-2)^3 = -8, any other exponent for them gives a NaN component
+            let exponent = number_or_vector3_from_lua(exponent)?;
+            Ok(Vector3(Vec3::new(
+                this.0.x.powf(exponent.x),
+                this.0.y.powf(exponent.y),
+                this.0.z.powf(exponent.z),
+            )))
+        });
+        methods.add_method(
+            "projectOnPlane",
+            |_, this, normal: LuaUserDataRef<Vector3>| {
+                // A zero normal does not describe any plane, leave the vector as-is
+                let normal = normal.0.normalize_or_zero();
+                Ok(Vector3(this.0 - this.0.dot(normal) * normal))
+            },
+        );
+        methods.add_method("reflect", |_, this, normal: LuaUserDataRef<Vector3>| {
+            let normal = normal.0.normalize_or_zero();
+            Ok(Vector3(this.0 - 2.0 * this.0.dot(normal) * normal))
+        });
+        methods.add_method(
+            "rotateTowards",
+            |_, this, (target, max_radians): (LuaUserDataRef<Vector3>, f32)| {
+                if max_radians.is_nan() || max_radians < 0.0 {
+                    return Err(LuaError::RuntimeError(format!(
+                        "Max angle must be a non-negative number, got {max_radians}"
+                    )));
+                }
+                Ok(Vector3(rotate_towards(this.0, target.0, max_radians)))
+            },
+        );
+        methods.add_method(
+            "signedAngle",
+            |_, this, (rhs, axis): (LuaUserDataRef<Vector3>, LuaUserDataRef<Vector3>)| {
+                // Using atan2 instead of acos keeps this well-defined for (near) parallel
+                // vectors, the axis only decides the sign using the right-hand rule, so
+                // that an axis that is not perpendicular to both vectors does not shrink
+                // the angle, and without a usable axis this falls back to the unsigned angle
+                let cross = this.0.cross(rhs.0);
+                let sin = if axis.0 == Vec3::ZERO {
+                    cross.length()
+                } else {
+                    cross.length().copysign(cross.dot(axis.0))
+                };
+                let cos = this.0.dot(rhs.0);
+                Ok(sin.atan2(cos))
+            },
+        );
+        methods.add_method(
+            "snapToGrid",
+            |_, this, (grid_size, origin): (LuaValue, Option<LuaUserDataRef<Vector3>>)| {
+                let grid_size = grid_size_from_lua(grid_size)?;
+                let origin = origin.map(|o| o.0).unwrap_or_default();
+                Ok(Vector3(
+                    origin + ((this.0 - origin) / grid_size).round() * grid_size,
+                ))
+            },
+        );
+        methods.add_method("toEulerFromDirection", |_, this, ()| {
+            euler_from_direction(this.0)
+        });
+        methods.add_method("toNormalId", |_, this, ()| {
+            let direction = this.0.normalize_or_zero();
+            if direction == Vec3::ZERO {
+                return Ok(None);
+            }
+            // Ties are broken in favor of the earlier normal id, in enum order
+            let mut closest = NORMAL_ID_DIRECTIONS[0];
+            for candidate in &NORMAL_ID_DIRECTIONS[1..] {
+                if candidate.1.dot(direction) > closest.1.dot(direction) {
+                    closest = *candidate;
+                }
+            }
+            let name = closest.0;
+            EnumItem::from_enum_name_and_name("NormalId", name)
+                .map(Some)
+                .ok_or_else(|| {
+                    LuaError::RuntimeError(format!(
+                        "Enum.NormalId does not contain an item named {name}"
+                    ))
+                })
+        });
+        methods.add_method("toTable", |lua, this, ()| {
+            TableBuilder::new(lua)?
+                .with_value("x", this.0.x)?
+                .with_value("y", this.0.y)?
+                .with_value("z", this.0.z)?
+                .build()
+        });
+        methods.add_method("unitOr", |_, this, fallback: LuaUserDataRef<Vector3>| {
+            // Normalizing a (near) zero vector would give NaN components
+            if this.0.length_squared() <= f32::EPSILON {
+                Ok(*fallback)
+            } else {
+                Ok(Vector3(this.0.normalize()))
+            }
+        });
+        methods.add_method("withX", |_, this, x: f32| Ok(Vector3(this.0.with_x(x))));
+        methods.add_method("withY", |_, this, y: f32| Ok(Vector3(this.0.with_y(y))));
+        methods.add_method("withZ", |_, this, z: f32| Ok(Vector3(this.0.with_z(z))));
+        methods.add_method(
+            "writeToBuffer",
+            |lua, this, (buffer, offset): (LuaValue, Option<usize>)| {
+                let offset = offset.unwrap_or_default();
+                let (buffer_lib, buffer) = ensure_buffer_bounds(lua, buffer, offset)?;
+                let write_f32 = buffer_lib.get::<_, LuaFunction>("writef32")?;
+                write_f32.call::<_, ()>((buffer.clone(), offset, this.0.x))?;
+                write_f32.call::<_, ()>((buffer.clone(), offset + 4, this.0.y))?;
+                write_f32.call::<_, ()>((buffer, offset + 8, this.0.z))?;
+                Ok(())
+            },
+        );
         // Metamethods
         methods.add_meta_method(LuaMetaMethod::Eq, userdata_impl_eq);
         methods.add_meta_method(LuaMetaMethod::ToString, userdata_impl_to_string);
@@ -147,6 +334,179 @@ impl LuaUserData for Vector3 {
     }
 }
 
+/**
+    The default tolerance for `Vector3:isAxisAligned`, applied to each axis of the unit vector.
+*/
+const AXIS_ALIGNED_EPSILON: f32 = 1e-4;
+
+/**
+    The direction vector for each item of `Enum.NormalId`, in enum order.
+*/
+const NORMAL_ID_DIRECTIONS: [(&str, Vec3); 6] = [
+    ("Right", Vec3::X),
+    ("Top", Vec3::Y),
+    ("Back", Vec3::Z),
+    ("Left", Vec3::NEG_X),
+    ("Bottom", Vec3::NEG_Y),
+    ("Front", Vec3::NEG_Z),
+];
+
+/**
+    Gets the pitch & yaw angles, in radians, that orient a forward
+    vector towards the given direction, returned as `(pitch, yaw)`.
+
+    The forward vector is `-Z`, same as the `LookVector` of a `CFrame`, and
+    the angles are applied yaw first, meaning that passing them to
+    `CFrame.fromOrientation(pitch, yaw, 0)` will look along the direction.
+
+    Positive pitch looks up, and positive yaw turns towards `-X`. When looking
+    straight up or down the yaw is ambiguous, and will always be zero.
+*/
+fn euler_from_direction(direction: Vec3) -> LuaResult<(f32, f32)> {
+    let direction = direction.normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return Err(LuaError::RuntimeError(
+            "Direction must be a non-zero and finite vector".to_string(),
+        ));
+    }
+
+    let pitch = direction.y.clamp(-1.0, 1.0).asin();
+    let yaw = if direction.x.hypot(direction.z) <= f32::EPSILON {
+        0.0
+    } else {
+        // Negative zero on the x axis turns into -PI here, we want
+        // to keep the yaw within (-PI, PI] so that looking down +Z
+        // consistently gives the same angle no matter the sign
+        match (-direction.x).atan2(-direction.z) {
+            yaw if yaw <= -PI => PI,
+            yaw => yaw,
+        }
+    };
+
+    Ok((pitch, yaw))
+}
+
+/**
+    Rotates the direction of `from` towards the direction of `to`, by at most
+    `max_radians`, keeping the magnitude of `from`. Returns the direction of `to`,
+    at the magnitude of `from`, if it is already within the given angle.
+
+    Vectors pointing in opposite directions have no single rotation axis between
+    them, so these are rotated around an arbitrary, but stable, perpendicular axis.
+*/
+fn rotate_towards(from: Vec3, to: Vec3, max_radians: f32) -> Vec3 {
+    let length = from.length();
+    let (from_unit, to_unit) = (from.normalize_or_zero(), to.normalize_or_zero());
+    if from_unit == Vec3::ZERO || to_unit == Vec3::ZERO {
+        return from;
+    }
+
+    let angle = from_unit.angle_between(to_unit);
+    if angle <= max_radians {
+        return to_unit * length;
+    }
+
+    let axis = from_unit.cross(to_unit).normalize_or_zero();
+    let axis = if axis == Vec3::ZERO {
+        from_unit.any_orthonormal_vector()
+    } else {
+        axis
+    };
+    Quat::from_axis_angle(axis, max_radians) * from_unit * length
+}
+
+/**
+    Gets the grid size for snapping a vector to a grid, which may
+    either be a single number for all axes, or a `Vector3` per axis.
+
+    Every axis of the grid size must be finite and non-zero.
+*/
+fn grid_size_from_lua(value: LuaValue) -> LuaResult<Vec3> {
+    let grid_size = number_or_vector3_from_lua(value)?;
+    if !grid_size.is_finite() || grid_size.cmpeq(Vec3::ZERO).any() {
+        return Err(LuaError::RuntimeError(format!(
+            "Grid size must be finite and non-zero on all axes, got {}",
+            Vector3(grid_size)
+        )));
+    }
+    Ok(grid_size)
+}
+
+/**
+    Gets a value per axis from either a single number for all axes, or a `Vector3`.
+*/
+fn number_or_vector3_from_lua(value: LuaValue) -> LuaResult<Vec3> {
+    match &value {
+        LuaValue::Integer(i) => Ok(Vec3::splat(*i as f32)),
+        LuaValue::Number(n) => Ok(Vec3::splat(*n as f32)),
+        LuaValue::UserData(ud) if ud.is::<Vector3>() => Ok(ud.borrow::<Vector3>()?.0),
+        _ => Err(LuaError::FromLuaConversionError {
+            from: value.type_name(),
+            to: "Vector3",
+            message: Some(format!(
+                "Expected number or Vector3, got {}",
+                value.type_name()
+            )),
+        }),
+    }
+}
+
+/**
+    Makes sure that the given value is a buffer, and that three
+    consecutive `f32` values fit in it at the given byte offset.
+
+    Returns the builtin `buffer` library along with the buffer, same as [`ensure_buffer`].
+*/
+fn ensure_buffer_bounds<'lua>(
+    lua: &'lua Lua,
+    buffer: LuaValue<'lua>,
+    offset: usize,
+) -> LuaResult<(LuaTable<'lua>, LuaValue<'lua>)> {
+    let (buffer_lib, buffer, buffer_len) = ensure_buffer(lua, buffer)?;
+
+    if offset.saturating_add(BUFFER_VECTOR3_SIZE) > buffer_len {
+        return Err(LuaError::RuntimeError(format!(
+            "Buffer offset {offset} is out of bounds - a Vector3 needs {BUFFER_VECTOR3_SIZE} bytes, buffer length is {buffer_len}"
+        )));
+    }
+
+    Ok((buffer_lib, buffer))
+}
+
+/**
+    Computes the magnitude of every `Vector3` in a flat buffer of
+    little-endian `f32` components, laid out as `x, y, z, x, y, z, ...`
+
+    The buffer length must be a multiple of [`BUFFER_VECTOR3_SIZE`].
+
+    Vectors are processed four at a time, transposed into one `Vec4` per
+    axis so that all four magnitudes are computed using SIMD lanes, and
+    any remaining vectors at the end are computed one by one.
+*/
+fn batch_magnitudes(bytes: &[u8]) -> Vec<f32> {
+    const LANES: usize = 4;
+
+    let chunks = bytes.chunks_exact(BUFFER_VECTOR3_SIZE * LANES);
+    let tail = chunks.remainder();
+
+    let mut magnitudes = Vec::with_capacity(bytes.len() / BUFFER_VECTOR3_SIZE);
+    for chunk in chunks {
+        let mut vectors = read_vector3s(chunk);
+        let [a, b, c, d]: [Vec3; LANES] =
+            std::array::from_fn(|_| vectors.next().expect("chunk contains exactly four vectors"));
+
+        let xs = Vec4::new(a.x, b.x, c.x, d.x);
+        let ys = Vec4::new(a.y, b.y, c.y, d.y);
+        let zs = Vec4::new(a.z, b.z, c.z, d.z);
+
+        let squared = xs * xs + ys * ys + zs * zs;
+        magnitudes.extend(squared.to_array().map(f32::sqrt));
+    }
+    magnitudes.extend(read_vector3s(tail).map(Vec3::length));
+
+    magnitudes
+}
+
 impl fmt::Display for Vector3 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}, {}, {}", self.0.x, self.0.y, self.0.z)
@@ -202,6 +562,23 @@ impl ops::Div<f32> for Vector3 {
     }
 }
 
+impl Serialize for Vector3 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Vector3", 3)?;
+        state.serialize_field("x", &self.0.x)?;
+        state.serialize_field("y", &self.0.y)?;
+        state.serialize_field("z", &self.0.z)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Vector3 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y, z] = deserialize_vector_components(deserializer, "Vector3", &["x", "y", "z"])?;
+        Ok(Vector3(Vec3::new(x, y, z)))
+    }
+}
+
 impl From<DomVector3> for Vector3 {
     fn from(v: DomVector3) -> Self {
         Vector3(Vec3 {
@@ -221,3 +598,98 @@ impl From<Vector3> for DomVector3 {
         }
     }
 }
+
+#[cfg(test)]
+mod vector3_test {
+    use std::time::Instant;
+
+    use glam::Vec3;
+    use mlua::prelude::*;
+
+    use crate::roblox::exports::LuaExportsTable;
+
+    use super::{batch_magnitudes, Vector3, BUFFER_VECTOR3_SIZE};
+
+    fn sample_points(count: usize) -> Vec<Vec3> {
+        (0..count)
+            .map(|i| {
+                let i = i as f32;
+                Vec3::new(i * 0.5 - 100.0, 10.0 - i, (i * 0.25).sin() * 3.0)
+            })
+            .collect()
+    }
+
+    fn to_bytes(points: &[Vec3]) -> Vec<u8> {
+        points
+            .iter()
+            .flat_map(|point| point.to_array())
+            .flat_map(f32::to_le_bytes)
+            .collect()
+    }
+
+    #[test]
+    fn batch_magnitudes_match_single() {
+        // Counts that are not multiples of four also exercise the tail
+        for count in [0, 1, 3, 4, 5, 64, 67] {
+            let points = sample_points(count);
+            let bytes = to_bytes(&points);
+            assert_eq!(bytes.len(), points.len() * BUFFER_VECTOR3_SIZE);
+
+            let magnitudes = batch_magnitudes(&bytes);
+            assert_eq!(magnitudes.len(), points.len());
+
+            for (point, magnitude) in points.iter().zip(magnitudes) {
+                assert!((point.length() - magnitude).abs() <= 1e-4 * point.length().max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn batch_magnitudes_benchmark() -> LuaResult<()> {
+        const ITERATIONS: usize = 100;
+
+        let lua = Lua::new();
+        lua.globals()
+            .set("Vector3", Vector3::create_exports_table(&lua)?)?;
+
+        let points = sample_points(1_000);
+        let buffer = lua
+            .load("return buffer.fromstring(...)")
+            .call::<_, LuaValue>(lua.create_string(to_bytes(&points))?)?;
+
+        let per_vector = lua
+            .load(
+                r#"
+                local points = ...
+                local magnitudes = {}
+                for offset = 0, buffer.len(points) - 1, 12 do
+                    table.insert(magnitudes, Vector3.fromBuffer(points, offset).Magnitude)
+                end
+                return magnitudes
+                "#,
+            )
+            .into_function()?;
+        let batched = lua
+            .load("return Vector3.magnitudesFromBuffer(...)")
+            .into_function()?;
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let magnitudes = per_vector.call::<_, LuaTable>(buffer.clone())?;
+            assert_eq!(magnitudes.raw_len(), points.len());
+        }
+        let per_vector = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let magnitudes = batched.call::<_, LuaTable>(buffer.clone())?;
+            assert_eq!(magnitudes.raw_len(), points.len());
+        }
+        let batched = start.elapsed();
+
+        println!("per-vector: {per_vector:?}, batched: {batched:?}");
+
+        Ok(())
+    }
+}
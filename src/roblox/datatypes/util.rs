@@ -1,3 +1,10 @@
+use std::fmt;
+
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserializer,
+};
+
 // HACK: We round to the nearest Very Small Decimal
 // to reduce writing out floating point accumulation
 // errors to files (mostly relevant for xml formats)
@@ -14,3 +21,124 @@ pub fn round_float_decimal(value: f32) -> f32 {
 
     whole + fract
 }
+
+/**
+    Deserializes the components of a vector datatype, such as `Vector3`.
+
+    In addition to the canonical form of a map with lowercase keys, this
+    also accepts maps with uppercase keys, and sequences of components,
+    so that vectors written by other tools can be read without changes.
+*/
+pub fn deserialize_vector_components<'de, D, const N: usize>(
+    deserializer: D,
+    datatype: &'static str,
+    names: &'static [&'static str; N],
+) -> Result<[f32; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(VectorComponentsVisitor { datatype, names })
+}
+
+struct VectorComponentsVisitor<const N: usize> {
+    datatype: &'static str,
+    names: &'static [&'static str; N],
+}
+
+impl<'de, const N: usize> Visitor<'de> for VectorComponentsVisitor<N> {
+    type Value = [f32; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a {} as a map of components or a sequence of {N} numbers",
+            self.datatype
+        )
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut components = [0.0; N];
+        for (index, component) in components.iter_mut().enumerate() {
+            *component = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(components)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = [None; N];
+        while let Some(key) = map.next_key::<String>()? {
+            // Keys other than the component names are ignored, other tools may add their own
+            let Some(index) = self
+                .names
+                .iter()
+                .position(|name| key.eq_ignore_ascii_case(name))
+            else {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+            if components[index].is_some() {
+                return Err(de::Error::duplicate_field(self.names[index]));
+            }
+            components[index] = Some(map.next_value()?);
+        }
+        let mut values = [0.0; N];
+        for (index, component) in components.into_iter().enumerate() {
+            values[index] = component.ok_or_else(|| de::Error::missing_field(self.names[index]))?;
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod vector_serde_test {
+    use glam::{Vec2, Vec3};
+
+    use crate::roblox::datatypes::types::{Vector2, Vector3};
+
+    #[test]
+    fn serializes_lowercase_keys() {
+        let json = serde_json::to_string(&Vector3(Vec3::new(1.0, 2.5, -3.0))).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":2.5,"z":-3.0}"#);
+
+        let json = serde_json::to_string(&Vector2(Vec2::new(4.0, 5.0))).unwrap();
+        assert_eq!(json, r#"{"x":4.0,"y":5.0}"#);
+    }
+
+    #[test]
+    fn deserializes_alternate_shapes() {
+        let expected = Vector3(Vec3::new(1.0, 2.0, 3.0));
+        for json in [
+            r#"{"x":1,"y":2,"z":3}"#,
+            r#"{"X":1,"Y":2,"Z":3}"#,
+            r#"{"x":1,"Y":2,"z":3,"name":"ignored"}"#,
+            r#"[1,2,3]"#,
+        ] {
+            let vector: Vector3 = serde_json::from_str(json).unwrap();
+            assert_eq!(vector, expected, "{json}");
+        }
+
+        let expected = Vector2(Vec2::new(-1.0, 0.5));
+        for json in [r#"{"X":-1,"Y":0.5}"#, r#"[-1,0.5]"#] {
+            let vector: Vector2 = serde_json::from_str(json).unwrap();
+            assert_eq!(vector, expected, "{json}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_shapes() {
+        for json in [
+            r#"{"x":1,"y":2}"#,
+            r#"{"x":1,"X":1,"y":2,"z":3}"#,
+            r#"[1,2]"#,
+            r#"[1,2,3,4]"#,
+            r#""1,2,3""#,
+        ] {
+            assert!(serde_json::from_str::<Vector3>(json).is_err(), "{json}");
+        }
+    }
+}
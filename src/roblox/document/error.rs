@@ -9,6 +9,12 @@ pub enum DocumentError {
     UnknownFormat,
     #[error("Failed to read document from buffer - {0}")]
     ReadError(String),
+    #[error("Failed to read document - class '{0}' is not in the reflection database")]
+    UnknownClass(String),
+    #[error(
+        "Failed to read document - property '{1}' of class '{0}' is not in the reflection database"
+    )]
+    UnknownProperty(String, String),
     #[error("Failed to write document to buffer - {0}")]
     WriteError(String),
     #[error("Failed to convert into a DataModel - the given document is not a place")]
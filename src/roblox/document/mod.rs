@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 use rbx_dom_weak::{types::Ref as DomRef, InstanceBuilder as DomInstanceBuilder, WeakDom};
 use rbx_xml::{
     DecodeOptions as XmlDecodeOptions, DecodePropertyBehavior as XmlDecodePropertyBehavior,
@@ -8,10 +10,12 @@ mod error;
 mod format;
 mod kind;
 mod postprocessing;
+mod reflection_miss;
 
 pub use error::*;
 pub use format::*;
 pub use kind::*;
+pub use reflection_miss::*;
 
 use postprocessing::*;
 
@@ -131,6 +135,22 @@ impl Document {
         Ok(Self { kind, format, dom })
     }
 
+    /**
+        Decodes and creates a new document from a byte buffer, handling any classes and
+        properties that are missing from the reflection database using the given behavior.
+
+        See [`Document::from_bytes`] and [`ReflectionMissBehavior`] for more details.
+    */
+    pub fn from_bytes_with_reflection_misses(
+        bytes: impl AsRef<[u8]>,
+        kind: DocumentKind,
+        behavior: ReflectionMissBehavior,
+    ) -> DocumentResult<Self> {
+        let (format, mut dom) = Self::from_bytes_inner(bytes)?;
+        behavior.apply(&mut dom)?;
+        Ok(Self { kind, format, dom })
+    }
+
     /**
         Encodes the document as a vector of bytes, to
         be written to a file or sent over the network.
@@ -149,24 +169,44 @@ impl Document {
     */
     pub fn to_bytes_with_format(&self, format: DocumentFormat) -> DocumentResult<Vec<u8>> {
         let mut bytes = Vec::new();
+        self.write_with_format(&mut bytes, format)?;
+        Ok(bytes)
+    }
+
+    /**
+        Gets the number of bytes the document would be encoded as,
+        without keeping the encoded bytes around in memory.
+    */
+    pub fn serialized_size_with_format(&self, format: DocumentFormat) -> DocumentResult<usize> {
+        let mut counter = ByteCounter::default();
+        self.write_with_format(&mut counter, format)?;
+        Ok(counter.0)
+    }
+
+    fn write_with_format(&self, writer: impl Write, format: DocumentFormat) -> DocumentResult<()> {
         match format {
             DocumentFormat::Binary => {
-                rbx_binary::to_writer(&mut bytes, &self.dom, self.dom.root().children())
+                rbx_binary::to_writer(writer, &self.dom, self.dom.root().children())
                     .map_err(|err| DocumentError::WriteError(err.to_string()))
             }
             DocumentFormat::Xml => {
                 let xml_options = XmlEncodeOptions::new()
                     .property_behavior(XmlEncodePropertyBehavior::WriteUnknown);
-                rbx_xml::to_writer(
-                    &mut bytes,
-                    &self.dom,
-                    self.dom.root().children(),
-                    xml_options,
-                )
-                .map_err(|err| DocumentError::WriteError(err.to_string()))
+                rbx_xml::to_writer(writer, &self.dom, self.dom.root().children(), xml_options)
+                    .map_err(|err| DocumentError::WriteError(err.to_string()))
             }
-        }?;
-        Ok(bytes)
+        }
+    }
+
+    /**
+        Removes all properties that are equal to their default value, according to
+        the reflection database, from all instances in the document.
+
+        This produces smaller files, same as the optimized saving in Roblox Studio,
+        and the removed properties will get their default values back when loaded.
+    */
+    pub fn strip_default_properties(&mut self) {
+        strip_default_properties(&mut self.dom);
     }
 
     /**
@@ -288,3 +328,20 @@ impl Document {
         })
     }
 }
+
+/**
+    A writer that discards everything written to it, only counting the bytes.
+*/
+#[derive(Debug, Default)]
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
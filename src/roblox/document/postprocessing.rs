@@ -1,9 +1,15 @@
 use rbx_dom_weak::{
-    types::{Ref as DomRef, VariantType as DomType},
+    types::{
+        CFrame as DomCFrame, Ref as DomRef, Variant as DomValue, VariantType as DomType,
+        Vector3 as DomVector3,
+    },
     Instance as DomInstance, WeakDom,
 };
 
-use crate::roblox::shared::instance::class_is_a;
+use crate::roblox::{
+    datatypes::types::{CFrame, Color3, Vector3},
+    shared::instance::{class_is_a, find_property_info},
+};
 
 pub fn postprocess_dom_for_place(_dom: &mut WeakDom) {
     // Nothing here yet
@@ -24,6 +30,50 @@ pub fn postprocess_dom_for_model(dom: &mut WeakDom) {
     });
 }
 
+pub fn strip_default_properties(dom: &mut WeakDom) {
+    let root_ref = dom.root_ref();
+    recurse_instances(dom, root_ref, &|inst| {
+        // Properties that are missing from a file get their default value
+        // when loaded, so writing the ones that equal it is redundant
+        let class_name = inst.class.clone();
+        inst.properties.retain(|name, value| {
+            !find_property_info(&class_name, name).is_some_and(|info| {
+                match (value, info.value_default, info.enum_default) {
+                    (DomValue::Enum(value), _, Some(default)) => value.to_u32() == default,
+                    (value, Some(default), _) => dom_values_equal(value, default),
+                    _ => false,
+                }
+            })
+        });
+    });
+}
+
+/**
+    Compares two dom values using the equality of their datatypes.
+
+    Values set from Lua may be stored as a different type than the
+    reflection default, such as `Color3` instead of `Color3uint8`, or
+    may have been rounded when stored, so comparing them directly is
+    not enough to tell if a property still has its default value.
+*/
+fn dom_values_equal(value: &DomValue, default: &DomValue) -> bool {
+    match (value, default) {
+        (DomValue::Color3(value), DomValue::Color3uint8(default)) => {
+            Color3::from(*value) == Color3::from(*default)
+        }
+        (DomValue::Color3uint8(value), DomValue::Color3(default)) => {
+            Color3::from(*value) == Color3::from(*default)
+        }
+        (DomValue::Vector3(value), DomValue::Vector3(default)) => {
+            DomVector3::from(Vector3::from(*value)) == DomVector3::from(Vector3::from(*default))
+        }
+        (DomValue::CFrame(value), DomValue::CFrame(default)) => {
+            DomCFrame::from(CFrame::from(*value)) == DomCFrame::from(CFrame::from(*default))
+        }
+        (value, default) => value == default,
+    }
+}
+
 fn recurse_instances<F>(dom: &mut WeakDom, dom_ref: DomRef, f: &F)
 where
     F: Fn(&mut DomInstance) + 'static,
@@ -0,0 +1,92 @@
+use mlua::prelude::*;
+use rbx_dom_weak::WeakDom;
+
+use crate::roblox::shared::instance::find_property_info;
+
+use super::{DocumentError, DocumentResult};
+
+/**
+    What to do with classes and properties that are missing
+    from the bundled reflection database when reading a document.
+
+    Valid variants are the following:
+
+    - `Keep` - keeps them as they are, without any type information
+    - `Skip` - removes instances of unknown classes, along with their descendants,
+      as well as any unknown properties on instances of known classes
+    - `Error` - fails to read the document at the first unknown class or property
+
+    Documents made with newer versions of Roblox Studio may contain either of these.
+*/
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum ReflectionMissBehavior {
+    #[default]
+    Keep,
+    Skip,
+    Error,
+}
+
+impl ReflectionMissBehavior {
+    /**
+        Applies this behavior to all instances in the given weak dom,
+        not including the root instance, which is never a real instance.
+    */
+    pub(super) fn apply(self, dom: &mut WeakDom) -> DocumentResult<()> {
+        if self == Self::Keep {
+            return Ok(());
+        }
+        let db = rbx_reflection_database::get();
+        let mut stack = dom.root().children().to_vec();
+        while let Some(dom_ref) = stack.pop() {
+            let Some(inst) = dom.get_by_ref_mut(dom_ref) else {
+                continue;
+            };
+            if !db.classes.contains_key(inst.class.as_str()) {
+                match self {
+                    Self::Error => return Err(DocumentError::UnknownClass(inst.class.clone())),
+                    _ => dom.destroy(dom_ref),
+                }
+                continue;
+            }
+            let class_name = inst.class.clone();
+            let is_known_property = |name: &str| {
+                // Attributes and tags are not described by the reflection database
+                matches!(name, "Attributes" | "Tags")
+                    || find_property_info(&class_name, name).is_some()
+            };
+            if self == Self::Error {
+                if let Some(name) = inst.properties.keys().find(|name| !is_known_property(name)) {
+                    return Err(DocumentError::UnknownProperty(
+                        class_name.clone(),
+                        name.clone(),
+                    ));
+                }
+            } else {
+                inst.properties.retain(|name, _| is_known_property(name));
+            }
+            stack.extend(inst.children().iter().copied());
+        }
+        Ok(())
+    }
+}
+
+impl<'lua> FromLua<'lua> for ReflectionMissBehavior {
+    fn from_lua(value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
+        let LuaValue::String(s) = &value else {
+            return Err(LuaError::FromLuaConversionError {
+                from: value.type_name(),
+                to: "ReflectionMissBehavior",
+                message: Some(format!("Expected string, got {}", value.type_name())),
+            });
+        };
+        let name = s.to_str()?;
+        match name.to_ascii_lowercase().as_str() {
+            "keep" => Ok(Self::Keep),
+            "skip" => Ok(Self::Skip),
+            "error" => Ok(Self::Error),
+            _ => Err(LuaError::RuntimeError(format!(
+                "Invalid reflection miss behavior '{name}' - expected Keep, Skip or Error"
+            ))),
+        }
+    }
+}
@@ -9,10 +9,14 @@ use crate::roblox::{
     datatypes::{
         attributes::{ensure_valid_attribute_name, ensure_valid_attribute_value},
         conversion::{DomValueToLua, LuaToDomValue},
-        types::EnumItem,
+        types::{BrickColor, Color3, EnumItem},
         userdata_impl_eq, userdata_impl_to_string,
     },
-    shared::instance::{class_is_a, find_property_info},
+    document::{Document, DocumentFormat},
+    shared::instance::{
+        class_is_a, ensure_property_writable, find_canonical_property_name, find_property_info,
+        PropertyInfo, StrictPropertyWrites,
+    },
 };
 
 use super::{data_model, registry::InstanceRegistry, Instance};
@@ -37,14 +41,39 @@ pub fn add_methods<'lua, M: LuaUserDataMethods<'lua, Instance>>(m: &mut M) {
         this.clear_all_children();
         Ok(())
     });
-    m.add_method("GetChildren", |lua, this, ()| {
-        ensure_not_destroyed(this)?;
-        this.get_children().into_lua(lua)
-    });
+    m.add_method(
+        "GetChildren",
+        |lua, this, filter: Option<InstanceFilter>| {
+            ensure_not_destroyed(this)?;
+            let children = this.get_children();
+            match filter {
+                None => children.into_lua(lua),
+                Some(filter) => filter.retain(children)?.into_lua(lua),
+            }
+        },
+    );
     m.add_method("GetDescendants", |lua, this, ()| {
         ensure_not_destroyed(this)?;
         this.get_descendants().into_lua(lua)
     });
+    m.add_method("buildClassIndex", |_, this, ()| {
+        ensure_not_destroyed(this)?;
+        this.build_class_index();
+        Ok(())
+    });
+    m.add_method("getDescendantsOfClass", |lua, this, class_name: String| {
+        ensure_not_destroyed(this)?;
+        this.get_descendants_of_class(class_name).into_lua(lua)
+    });
+    m.add_method("iterDescendants", |lua, this, ()| {
+        ensure_not_destroyed(this)?;
+        let mut cursor = this.iter_descendants();
+        lua.create_function_mut(move |_, ()| cursor.advance())
+    });
+    m.add_method("estimateSerializedSize", |_, this, as_xml: Option<bool>| {
+        ensure_not_destroyed(this)?;
+        instance_estimate_serialized_size(this, as_xml.unwrap_or_default())
+    });
     m.add_method("GetFullName", |lua, this, ()| {
         ensure_not_destroyed(this)?;
         this.get_full_name().into_lua(lua)
@@ -74,9 +103,16 @@ pub fn add_methods<'lua, M: LuaUserDataMethods<'lua, Instance>>(m: &mut M) {
     );
     m.add_method(
         "FindFirstChild",
-        |lua, this, (name, recursive): (String, Option<bool>)| {
+        |lua, this, (name, recursive, case_insensitive): (String, Option<bool>, Option<bool>)| {
             ensure_not_destroyed(this)?;
-            let predicate = |child: &DomInstance| child.name == name;
+            let case_insensitive = matches!(case_insensitive, Some(true));
+            let predicate = |child: &DomInstance| {
+                if case_insensitive {
+                    names_eq_ignore_case(&child.name, &name)
+                } else {
+                    child.name == name
+                }
+            };
             if matches!(recursive, Some(true)) {
                 this.find_descendant(predicate).into_lua(lua)
             } else {
@@ -182,7 +218,7 @@ pub fn add_methods<'lua, M: LuaUserDataMethods<'lua, Instance>>(m: &mut M) {
     });
 }
 
-fn ensure_not_destroyed(inst: &Instance) -> LuaResult<()> {
+pub(crate) fn ensure_not_destroyed(inst: &Instance) -> LuaResult<()> {
     if inst.is_destroyed() {
         Err(LuaError::RuntimeError(
             "Instance has been destroyed".to_string(),
@@ -192,17 +228,96 @@ fn ensure_not_destroyed(inst: &Instance) -> LuaResult<()> {
     }
 }
 
+/**
+    Checks if two instance names are equal, ignoring case.
+
+    Characters are lowercased one at a time while comparing, so that
+    no new strings need to be allocated for every instance compared.
+*/
+fn names_eq_ignore_case(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+/**
+    A filter for instances, given either as a class name that
+    instances must be a subclass of, or a predicate function.
+*/
+enum InstanceFilter<'lua> {
+    ClassName(String),
+    Predicate(LuaFunction<'lua>),
+}
+
+impl InstanceFilter<'_> {
+    fn retain(&self, instances: Vec<Instance>) -> LuaResult<Vec<Instance>> {
+        let mut retained = Vec::with_capacity(instances.len());
+        for instance in instances {
+            let matches = match self {
+                Self::ClassName(class_name) => {
+                    class_is_a(instance.get_class_name(), class_name).unwrap_or(false)
+                }
+                Self::Predicate(predicate) => !matches!(
+                    predicate.call::<_, LuaValue>(instance.clone())?,
+                    LuaValue::Nil | LuaValue::Boolean(false)
+                ),
+            };
+            if matches {
+                retained.push(instance);
+            }
+        }
+        Ok(retained)
+    }
+}
+
+impl<'lua> FromLua<'lua> for InstanceFilter<'lua> {
+    fn from_lua(value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::String(s) => Ok(Self::ClassName(s.to_str()?.to_string())),
+            LuaValue::Function(f) => Ok(Self::Predicate(f)),
+            _ => Err(LuaError::FromLuaConversionError {
+                from: value.type_name(),
+                to: "InstanceFilter",
+                message: Some(format!(
+                    "Expected class name or predicate function, got {}",
+                    value.type_name()
+                )),
+            }),
+        }
+    }
+}
+
+/**
+    Gets the number of bytes that the instance and its descendants would be serialized
+    as, as a place file for a `DataModel` and as a model file for any other instance.
+*/
+fn instance_estimate_serialized_size(this: &Instance, as_xml: bool) -> LuaResult<usize> {
+    let document = if this.get_class_name() == data_model::CLASS_NAME {
+        Document::from_data_model_instance(this.clone())?
+    } else {
+        Document::from_instance_array(vec![this.clone()])?
+    };
+    let format = if as_xml {
+        DocumentFormat::Xml
+    } else {
+        DocumentFormat::Binary
+    };
+    Ok(document.serialized_size_with_format(format)?)
+}
+
 /*
     Gets a property value for an instance.
 
     Getting a value does the following:
 
     1. Check if it is a special property like "ClassName", "Name" or "Parent"
-    2. Check if a property exists for the wanted name
-        2a. Get an existing instance property OR
-        2b. Get a property from a known default value
-    3. Get a current child of the instance
-    4. No valid property or instance found, throw error
+    2. Resolve the wanted name from a property alias to its canonical name
+    3. Check if a property exists for the wanted name
+        3a. Get a property that has been migrated to a new property OR
+        3b. Get an existing instance property OR
+        3c. Get a property from a known default value
+    4. Get a current child of the instance
+    5. No valid property or instance found, throw error
 */
 fn instance_property_get<'lua>(
     lua: &'lua Lua,
@@ -222,8 +337,12 @@ fn instance_property_get<'lua>(
         _ => {}
     }
 
+    let prop_name = find_canonical_property_name(&this.class_name, &prop_name).to_string();
+
     if let Some(info) = find_property_info(&this.class_name, &prop_name) {
-        if let Some(prop) = this.get_property(&prop_name) {
+        if let Some(migrated) = get_migrated_property(this, &info) {
+            Ok(LuaValue::dom_value_to_lua(lua, &migrated)?)
+        } else if let Some(prop) = this.get_property(&prop_name) {
             if let DomValue::Enum(enum_value) = prop {
                 let enum_name = info.enum_name.ok_or_else(|| {
                     LuaError::RuntimeError(format!(
@@ -288,9 +407,11 @@ fn instance_property_get<'lua>(
     Setting a value does the following:
 
     1. Check if it is a special property like "ClassName", "Name" or "Parent"
-    2. Check if a property exists for the wanted name
-        2a. Set a strict enum from a given EnumItem OR
-        2b. Set a normal property from a given value
+    2. Resolve the wanted name from a property alias to its canonical name
+    3. Check if a property exists for the wanted name
+        3a. Set a strict enum from a given EnumItem OR
+        3b. Set a property that has been migrated to a new property OR
+        3c. Set a normal property from a given value
 */
 fn instance_property_set<'lua>(
     lua: &'lua Lua,
@@ -318,13 +439,21 @@ fn instance_property_set<'lua>(
             }
             type Parent<'lua> = Option<LuaUserDataRef<'lua, Instance>>;
             let parent = Parent::from_lua(prop_value, lua)?;
-            this.set_parent(parent.map(|p| p.clone()));
-            return Ok(());
+            return this.try_set_parent(parent.map(|p| p.clone()));
         }
         _ => {}
     }
 
+    let prop_name = find_canonical_property_name(&this.class_name, &prop_name).to_string();
+
     if let Some(info) = find_property_info(&this.class_name, &prop_name) {
+        let strict = lua
+            .app_data_ref::<StrictPropertyWrites>()
+            .map(|s| *s)
+            .unwrap_or_default();
+        if strict.0 {
+            ensure_property_writable(&prop_name, &info).map_err(LuaError::RuntimeError)?;
+        }
         if let Some(enum_name) = info.enum_name {
             match LuaUserDataRef::<EnumItem>::from_lua(prop_value, lua) {
                 Ok(given_enum) if given_enum.parent.desc.name == enum_name => {
@@ -340,7 +469,9 @@ fn instance_property_set<'lua>(
         } else if let Some(dom_type) = info.value_type {
             match prop_value.lua_to_dom_value(lua, Some(dom_type)) {
                 Ok(dom_value) => {
-                    this.set_property(prop_name, dom_value);
+                    if !set_migrated_property(this, &info, &dom_value) {
+                        this.set_property(prop_name, dom_value);
+                    }
                     Ok(())
                 }
                 Err(e) => Err(e.into()),
@@ -360,3 +491,55 @@ fn instance_property_set<'lua>(
         )))
     }
 }
+
+/**
+    Gets the value of a property that has been migrated to a new property,
+    such as `BrickColor`, which is stored as `Color` on instances.
+
+    Returns `None` if the property has not been migrated, or if there is
+    no known conversion from the new property back to the old property.
+*/
+fn get_migrated_property(this: &Instance, info: &PropertyInfo) -> Option<DomValue> {
+    let new_name = &info.migration?.new_property_name;
+    let new_value = this.get_property(new_name).or_else(|| {
+        find_property_info(&this.class_name, new_name)
+            .and_then(|new_info| new_info.value_default)
+            .cloned()
+    })?;
+    match (info.value_type?, new_value) {
+        (DomType::BrickColor, DomValue::Color3(color)) => Some(DomValue::BrickColor(
+            BrickColor::from(Color3::from(color)).into(),
+        )),
+        (DomType::BrickColor, DomValue::Color3uint8(color)) => Some(DomValue::BrickColor(
+            BrickColor::from(Color3::from(color)).into(),
+        )),
+        _ => None,
+    }
+}
+
+/**
+    Sets the value of a property that has been migrated to a new property,
+    such as `BrickColor`, which is stored as `Color` on instances.
+
+    Returns `false` if the property has not been migrated, or if there is
+    no known conversion from the old property to the new property, in
+    which case the value should be set directly on the old property.
+*/
+fn set_migrated_property(this: &Instance, info: &PropertyInfo, value: &DomValue) -> bool {
+    let Some(migration) = info.migration else {
+        return false;
+    };
+    let new_name = &migration.new_property_name;
+    let new_type = find_property_info(&this.class_name, new_name).and_then(|i| i.value_type);
+    let new_value = match (value, new_type) {
+        (DomValue::BrickColor(color), Some(DomType::Color3)) => {
+            DomValue::Color3(Color3::from(BrickColor::from(*color)).into())
+        }
+        (DomValue::BrickColor(color), Some(DomType::Color3uint8)) => {
+            DomValue::Color3uint8(color.to_color3uint8())
+        }
+        _ => return false,
+    };
+    this.set_property(new_name, new_value);
+    true
+}
@@ -0,0 +1,50 @@
+use mlua::prelude::*;
+use rbx_dom_weak::types::Variant as DomValue;
+
+use crate::roblox::shared::classes::add_class_restricted_method;
+
+use super::Instance;
+
+pub const CLASS_NAME: &str = "LuaSourceContainer";
+
+pub fn add_methods<'lua, M: LuaUserDataMethods<'lua, Instance>>(m: &mut M) {
+    add_class_restricted_method(m, CLASS_NAME, "getSource", lua_source_container_get_source);
+    add_class_restricted_method(m, CLASS_NAME, "setSource", lua_source_container_set_source);
+}
+
+/**
+    Gets the source code of a script instance.
+
+    Model and place files may store the source as either a string,
+    binary string, or shared string, and all of these are supported.
+    A script with no source property will return an empty string.
+*/
+fn lua_source_container_get_source(_: &Lua, this: &Instance, _: ()) -> LuaResult<String> {
+    match this.get_property("Source") {
+        None => Ok(String::new()),
+        Some(DomValue::String(source)) => Ok(source),
+        Some(DomValue::BinaryString(source)) => bytes_to_source(source.as_ref()),
+        Some(DomValue::SharedString(source)) => bytes_to_source(source.data()),
+        Some(value) => Err(LuaError::RuntimeError(format!(
+            "Source property of {} has unexpected type {:?}",
+            this.get_name(),
+            value.ty()
+        ))),
+    }
+}
+
+/**
+    Sets the source code of a script instance.
+
+    The source is always stored as a string property, which
+    is what Roblox Studio uses when saving scripts to files.
+*/
+fn lua_source_container_set_source(_: &Lua, this: &Instance, source: String) -> LuaResult<()> {
+    this.set_property("Source", DomValue::String(source));
+    Ok(())
+}
+
+fn bytes_to_source(bytes: &[u8]) -> LuaResult<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| LuaError::RuntimeError("Source property is not valid UTF-8".to_string()))
+}
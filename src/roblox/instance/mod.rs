@@ -1,8 +1,11 @@
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fmt,
     hash::{Hash, Hasher},
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
 };
 
 use mlua::prelude::*;
@@ -20,6 +23,8 @@ use crate::{
 
 pub(crate) mod base;
 pub(crate) mod data_model;
+pub(crate) mod lua_source_container;
+pub(crate) mod model;
 pub(crate) mod terrain;
 pub(crate) mod workspace;
 
@@ -31,6 +36,171 @@ const PROPERTY_NAME_TAGS: &str = "Tags";
 static INTERNAL_DOM: Lazy<Mutex<WeakDom>> =
     Lazy::new(|| Mutex::new(WeakDom::new(DomInstanceBuilder::new("ROOT"))));
 
+// NOTE: Every instance has a structure generation, which is bumped any time instances
+// below it in the internal dom are moved around or destroyed, so that class indexes and
+// descendant cursors are only invalidated by changes to their own descendants.
+// Instances without an entry have not had any of their descendants changed yet.
+static STRUCTURE_GENERATION: AtomicU64 = AtomicU64::new(0);
+static SUBTREE_GENERATIONS: Lazy<Mutex<HashMap<DomRef, u64>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static CLASS_INDEXES: Lazy<Mutex<HashMap<DomRef, ClassIndex>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/**
+    A cache of all descendants of an instance, grouped by their class name.
+
+    The index is only valid for the structure generation of the instance it was built
+    at, and must be rebuilt if any of its descendants have been moved or destroyed since.
+*/
+struct ClassIndex {
+    generation: u64,
+    descendants: HashMap<String, Vec<DomRef>>,
+}
+
+impl ClassIndex {
+    fn build(dom: &WeakDom, dom_ref: DomRef) -> Self {
+        let mut descendants: HashMap<String, Vec<DomRef>> = HashMap::new();
+        for descendant_ref in descendant_refs(dom, dom_ref) {
+            let descendant = dom.get_by_ref(descendant_ref).unwrap();
+            descendants
+                .entry(descendant.class.clone())
+                .or_default()
+                .push(descendant_ref);
+        }
+        Self {
+            generation: subtree_generation(dom_ref),
+            descendants,
+        }
+    }
+
+    fn is_current(&self, dom_ref: DomRef) -> bool {
+        self.generation == subtree_generation(dom_ref)
+    }
+}
+
+/**
+    Gets the current structure generation of the given instance.
+*/
+fn subtree_generation(dom_ref: DomRef) -> u64 {
+    SUBTREE_GENERATIONS
+        .lock()
+        .expect("Failed to lock subtree generations")
+        .get(&dom_ref)
+        .copied()
+        .unwrap_or_default()
+}
+
+/**
+    Marks the structure of the internal dom as changed below the given parents, bumping the
+    structure generation of each parent and all of its ancestors, which invalidates their
+    class indexes and descendant cursors. Parents that no longer exist, or that do not exist yet, are skipped.
+
+    Generations and indexes for instances that no longer exist are removed completely.
+*/
+fn mark_structure_changed(dom: &WeakDom, parent_refs: impl IntoIterator<Item = DomRef>) {
+    let generation = STRUCTURE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let mut generations = SUBTREE_GENERATIONS
+        .lock()
+        .expect("Failed to lock subtree generations");
+    for parent_ref in parent_refs {
+        let mut ancestor_ref = parent_ref;
+        while let Some(ancestor) = dom.get_by_ref(ancestor_ref) {
+            // Ancestors that were already bumped, either through another parent
+            // or because of a parent cycle, also had all of their ancestors bumped
+            if generations.insert(ancestor_ref, generation) == Some(generation) {
+                break;
+            }
+            ancestor_ref = ancestor.parent();
+        }
+    }
+    generations.retain(|dom_ref, _| dom.get_by_ref(*dom_ref).is_some());
+    drop(generations);
+
+    let mut indexes = CLASS_INDEXES.lock().expect("Failed to lock class indexes");
+    indexes.retain(|dom_ref, _| dom.get_by_ref(*dom_ref).is_some());
+}
+
+/**
+    Gets the referents of all descendants of the given instance, using a breadth-first search.
+*/
+fn descendant_refs(dom: &WeakDom, dom_ref: DomRef) -> Vec<DomRef> {
+    let mut descendants = Vec::new();
+    let mut queue = VecDeque::from_iter(
+        dom.get_by_ref(dom_ref)
+            .expect("Failed to find instance in document")
+            .children(),
+    );
+
+    while let Some(queue_ref) = queue.pop_front() {
+        descendants.push(*queue_ref);
+        let queue_inst = dom.get_by_ref(*queue_ref).unwrap();
+        for queue_ref_inner in queue_inst.children().iter().rev() {
+            queue.push_back(queue_ref_inner);
+        }
+    }
+
+    descendants
+}
+
+/**
+    A cursor that lazily traverses the descendants of an instance, depth-first,
+    yielding each instance before any of its own descendants.
+
+    Only the current path from the root instance is kept around, so iterating
+    through a huge tree does not need to gather all of its descendants up front.
+    Each entry in the path is an instance along with the index of its next child
+    to visit, so the entry after it is always the child right before that index.
+*/
+#[derive(Debug)]
+pub struct DescendantsCursor {
+    root: DomRef,
+    generation: u64,
+    stack: Vec<(DomRef, usize)>,
+}
+
+impl DescendantsCursor {
+    /**
+        Advances the cursor, returning the next descendant, or `None` once all have been visited.
+
+        Errors if the root instance has been destroyed, or if any of its descendants have been
+        moved or destroyed since the cursor was created, since instances could otherwise be
+        skipped or visited twice. Changes anywhere else in the internal dom, as well as to
+        properties of the descendants, do not affect the traversal.
+    */
+    pub fn advance(&mut self) -> LuaResult<Option<Instance>> {
+        let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let is_unchanged = subtree_generation(self.root) == self.generation;
+        if dom.get_by_ref(self.root).is_none() || !is_unchanged {
+            return Err(LuaError::RuntimeError(
+                "Instances were moved or destroyed while iterating descendants".to_string(),
+            ));
+        }
+
+        let next_ref = loop {
+            let Some((parent_ref, child_index)) = self.stack.last_mut() else {
+                return Ok(None);
+            };
+            let parent = dom
+                .get_by_ref(*parent_ref)
+                .expect("Failed to find instance in document");
+            match parent.children().get(*child_index) {
+                Some(child_ref) => {
+                    *child_index += 1;
+                    break *child_ref;
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        };
+        self.stack.push((next_ref, 0));
+
+        drop(dom); // Self::new needs mutex handle, drop it first
+        Ok(Some(Instance::new(next_ref)))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Instance {
     pub(crate) dom_ref: DomRef,
@@ -161,6 +331,33 @@ impl Instance {
         cloned
     }
 
+    /**
+        Clones multiple instances and all of their descendants, and parents
+        the clones to the given parent, returning the cloned instances.
+
+        References between any of the cloned instances and their descendants are
+        rewritten to point to the clones, while references to other instances are
+        cleared, same as when the instances are serialized into a separate model.
+    */
+    pub fn clone_multiple_into(instances: &[Instance], parent: &Instance) -> Vec<Instance> {
+        let mut dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+
+        let referents = instances
+            .iter()
+            .map(|inst| inst.dom_ref)
+            .collect::<Vec<_>>();
+
+        let mut cloned_dom = WeakDom::new(DomInstanceBuilder::new("ROOT"));
+        let cloned = dom.clone_multiple_into_external(&referents, &mut cloned_dom);
+        for referent in cloned.iter() {
+            cloned_dom.transfer(*referent, &mut dom, parent.dom_ref);
+        }
+        mark_structure_changed(&dom, [parent.dom_ref]);
+
+        drop(dom); // Self::new needs mutex handle, drop it first
+        cloned.into_iter().map(Self::new).collect()
+    }
+
     /**
         Clones the instance and all of its descendants, and orphans it.
 
@@ -200,7 +397,12 @@ impl Instance {
         } else {
             let mut dom = INTERNAL_DOM.lock().expect("Failed to lock document");
 
+            let parent_ref = dom
+                .get_by_ref(self.dom_ref)
+                .expect("Failed to find instance in document")
+                .parent();
             dom.destroy(self.dom_ref);
+            mark_structure_changed(&dom, [parent_ref]);
             true
         }
     }
@@ -232,6 +434,7 @@ impl Instance {
         for child_ref in child_refs {
             dom.destroy(child_ref);
         }
+        mark_structure_changed(&dom, [self.dom_ref]);
     }
 
     /**
@@ -330,7 +533,56 @@ impl Instance {
             .map(|parent| parent.dom_ref)
             .unwrap_or_else(|| dom.root_ref());
 
+        let old_parent_ref = dom
+            .get_by_ref(self.dom_ref)
+            .expect("Failed to find instance in document")
+            .parent();
         dom.transfer_within(self.dom_ref, parent_ref);
+        mark_structure_changed(&dom, [old_parent_ref, parent_ref]);
+    }
+
+    /**
+        Sets the parent of the instance, same as [`Instance::set_parent`], but
+        errors instead if the new parent is the instance itself or one of its
+        descendants, since that would disconnect the instance from the tree.
+    */
+    pub fn try_set_parent(&self, parent: Option<Instance>) -> LuaResult<()> {
+        let mut dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let dom_root = dom.root_ref();
+
+        let parent_ref = parent
+            .map(|parent| parent.dom_ref)
+            .unwrap_or_else(|| dom.root_ref());
+
+        let mut seen = HashSet::new();
+        let mut ancestor_ref = parent_ref;
+        while ancestor_ref != dom_root && !ancestor_ref.is_none() && seen.insert(ancestor_ref) {
+            if ancestor_ref == self.dom_ref {
+                let name = |dom_ref| {
+                    dom.get_by_ref(dom_ref)
+                        .map(|inst| inst.name.clone())
+                        .unwrap_or_default()
+                };
+                return Err(LuaError::RuntimeError(format!(
+                    "Failed to set Parent of '{}' to '{}' - \
+                     an instance can not be parented to itself or its descendants",
+                    name(self.dom_ref),
+                    name(parent_ref),
+                )));
+            }
+            ancestor_ref = match dom.get_by_ref(ancestor_ref) {
+                Some(ancestor) => ancestor.parent(),
+                None => break,
+            };
+        }
+
+        let old_parent_ref = dom
+            .get_by_ref(self.dom_ref)
+            .expect("Failed to find instance in document")
+            .parent();
+        dom.transfer_within(self.dom_ref, parent_ref);
+        mark_structure_changed(&dom, [old_parent_ref, parent_ref]);
+        Ok(())
     }
 
     /**
@@ -556,26 +808,178 @@ impl Instance {
     */
     pub fn get_descendants(&self) -> Vec<Instance> {
         let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let descendants = descendant_refs(&dom, self.dom_ref);
 
-        let mut descendants = Vec::new();
-        let mut queue = VecDeque::from_iter(
-            dom.get_by_ref(self.dom_ref)
-                .expect("Failed to find instance in document")
-                .children(),
-        );
+        drop(dom); // Self::new needs mutex handle, drop it first
+        descendants.into_iter().map(Self::new).collect()
+    }
 
-        while let Some(queue_ref) = queue.pop_front() {
-            descendants.push(*queue_ref);
-            let queue_inst = dom.get_by_ref(*queue_ref).unwrap();
-            for queue_ref_inner in queue_inst.children().iter().rev() {
-                queue.push_back(queue_ref_inner);
+    /**
+        Builds an index of all current descendants of this `Instance`, grouped by class name.
+
+        Once built, [`Instance::get_descendants_of_class`] will use the index instead of
+        searching through all descendants. The index is invalidated any time descendants
+        of this instance are moved or destroyed, and will then be rebuilt the next time it
+        is used. Changes anywhere else in the internal dom do not invalidate the index.
+    */
+    pub fn build_class_index(&self) {
+        let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let index = ClassIndex::build(&dom, self.dom_ref);
+
+        let mut indexes = CLASS_INDEXES.lock().expect("Failed to lock class indexes");
+        indexes.insert(self.dom_ref, index);
+    }
+
+    /**
+        Gets all of the current descendants of this `Instance` with the exact given class name,
+        in the same order as [`Instance::get_descendants`].
+
+        This is a linear search through all descendants, unless a class
+        index has been built for this instance using [`Instance::build_class_index`].
+    */
+    pub fn get_descendants_of_class(&self, class_name: impl AsRef<str>) -> Vec<Instance> {
+        let class_name = class_name.as_ref();
+
+        let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let mut indexes = CLASS_INDEXES.lock().expect("Failed to lock class indexes");
+
+        let descendants = match indexes.get_mut(&self.dom_ref) {
+            Some(index) => {
+                if !index.is_current(self.dom_ref) {
+                    *index = ClassIndex::build(&dom, self.dom_ref);
+                }
+                index
+                    .descendants
+                    .get(class_name)
+                    .cloned()
+                    .unwrap_or_default()
             }
-        }
+            None => descendant_refs(&dom, self.dom_ref)
+                .into_iter()
+                .filter(|dom_ref| dom.get_by_ref(*dom_ref).unwrap().class == class_name)
+                .collect(),
+        };
 
+        drop(indexes);
         drop(dom); // Self::new needs mutex handle, drop it first
         descendants.into_iter().map(Self::new).collect()
     }
 
+    /**
+        Creates a cursor that lazily visits all of the descendants of this `Instance`.
+
+        See [`DescendantsCursor`] for the traversal order and its behavior when
+        descendants are moved or destroyed before the traversal has finished.
+    */
+    pub fn iter_descendants(&self) -> DescendantsCursor {
+        DescendantsCursor {
+            root: self.dom_ref,
+            generation: subtree_generation(self.dom_ref),
+            stack: vec![(self.dom_ref, 0)],
+        }
+    }
+
+    /**
+        Counts the given instances and all of their descendants by class name.
+
+        Instances that are given more than once, or that are descendants
+        of other given instances, will only be counted a single time.
+    */
+    pub fn count_classes(instances: &[Instance]) -> BTreeMap<String, usize> {
+        let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+
+        let mut counts = BTreeMap::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from_iter(instances.iter().map(|inst| inst.dom_ref));
+
+        while let Some(queue_ref) = queue.pop_front() {
+            if !visited.insert(queue_ref) {
+                continue;
+            }
+            let queue_inst = dom
+                .get_by_ref(queue_ref)
+                .expect("Failed to find instance in document");
+            *counts.entry(queue_inst.class.clone()).or_insert(0) += 1;
+            queue.extend(queue_inst.children());
+        }
+
+        counts
+    }
+
+    /**
+        Validates the given instances and all of their descendants, returning any issues found.
+
+        This checks for `Ref` properties pointing to instances outside of the given
+        trees, parent cycles, and instances with class names that do not exist.
+    */
+    pub fn validate_model(instances: &[Instance]) -> Vec<ModelIssue> {
+        let dom = INTERNAL_DOM.lock().expect("Failed to lock document");
+        let dom_root = dom.root_ref();
+
+        let mut issues = Vec::new();
+
+        // Any instance in a parent cycle that can be reached from the given
+        // instances must also be part of the cycle, so checking ancestors
+        // of only the given instances is enough to find all of them
+        for instance in instances {
+            let mut seen = HashSet::new();
+            let mut ancestor_ref = instance.dom_ref;
+            while ancestor_ref != dom_root && !ancestor_ref.is_none() {
+                if !seen.insert(ancestor_ref) {
+                    issues.push(ModelIssue::ParentCycle {
+                        instance: instance.dom_ref,
+                    });
+                    break;
+                }
+                ancestor_ref = match dom.get_by_ref(ancestor_ref) {
+                    Some(ancestor) => ancestor.parent(),
+                    None => break,
+                };
+            }
+        }
+
+        let mut tree = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from_iter(instances.iter().map(|inst| inst.dom_ref));
+        while let Some(queue_ref) = queue.pop_front() {
+            if !visited.insert(queue_ref) {
+                continue;
+            }
+            let queue_inst = dom
+                .get_by_ref(queue_ref)
+                .expect("Failed to find instance in document");
+            tree.push(queue_inst);
+            queue.extend(queue_inst.children());
+        }
+
+        for inst in tree {
+            if !class_exists(&inst.class) {
+                issues.push(ModelIssue::InvalidClassName {
+                    instance: inst.referent(),
+                    class_name: inst.class.clone(),
+                });
+            }
+
+            let mut dangling = inst
+                .properties
+                .iter()
+                .filter_map(|(name, value)| match value {
+                    DomValue::Ref(r) if !r.is_none() && !visited.contains(r) => Some(name),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            dangling.sort();
+            for property in dangling {
+                issues.push(ModelIssue::DanglingRef {
+                    instance: inst.referent(),
+                    property: property.clone(),
+                });
+            }
+        }
+
+        issues
+    }
+
     /**
         Gets the "full name" of this instance.
 
@@ -708,6 +1112,70 @@ impl Instance {
     }
 }
 
+/**
+    An issue found when validating instances using [`Instance::validate_model`].
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelIssue {
+    DanglingRef {
+        instance: DomRef,
+        property: String,
+    },
+    ParentCycle {
+        instance: DomRef,
+    },
+    InvalidClassName {
+        instance: DomRef,
+        class_name: String,
+    },
+}
+
+impl ModelIssue {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::DanglingRef { .. } => "DanglingRef",
+            Self::ParentCycle { .. } => "ParentCycle",
+            Self::InvalidClassName { .. } => "InvalidClassName",
+        }
+    }
+
+    fn instance_ref(&self) -> DomRef {
+        match self {
+            Self::DanglingRef { instance, .. }
+            | Self::ParentCycle { instance }
+            | Self::InvalidClassName { instance, .. } => *instance,
+        }
+    }
+}
+
+impl fmt::Display for ModelIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DanglingRef { property, .. } => write!(
+                f,
+                "Property '{property}' refers to an instance outside of the model"
+            ),
+            Self::ParentCycle { .. } => write!(f, "Instance is its own ancestor"),
+            Self::InvalidClassName { class_name, .. } => {
+                write!(f, "Class '{class_name}' does not exist")
+            }
+        }
+    }
+}
+
+impl<'lua> IntoLua<'lua> for ModelIssue {
+    fn into_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        let mut builder = TableBuilder::new(lua)?
+            .with_value("kind", self.kind())?
+            .with_value("instance", Instance::new(self.instance_ref()))?
+            .with_value("message", self.to_string())?;
+        if let Self::DanglingRef { property, .. } = self {
+            builder = builder.with_value("property", property)?;
+        }
+        builder.build()?.into_lua(lua)
+    }
+}
+
 impl LuaExportsTable<'_> for Instance {
     const EXPORT_NAME: &'static str = "Instance";
 
@@ -750,6 +1218,8 @@ impl LuaUserData for Instance {
     fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
         base::add_methods(methods);
         data_model::add_methods(methods);
+        lua_source_container::add_methods(methods);
+        model::add_methods(methods);
         terrain::add_methods(methods);
     }
 }
@@ -785,3 +1255,173 @@ impl From<Instance> for DomRef {
         value.dom_ref
     }
 }
+
+#[cfg(test)]
+mod class_index_test {
+    use std::time::Instant;
+
+    use super::{Instance, CLASS_INDEXES};
+
+    const CLASS_NAMES: [&str; 4] = ["Folder", "Model", "Part", "Script"];
+
+    fn create_tree(breadth: usize, depth: usize) -> Instance {
+        let root = Instance::new_orphaned("Folder");
+        let mut parents = vec![root.clone()];
+        for _ in 0..depth {
+            let mut children = Vec::new();
+            for parent in &parents {
+                for index in 0..breadth {
+                    let child = Instance::new_orphaned(CLASS_NAMES[index % CLASS_NAMES.len()]);
+                    child.set_parent(Some(parent.clone()));
+                    children.push(child);
+                }
+            }
+            parents = children;
+        }
+        root
+    }
+
+    fn linear_scan(root: &Instance, class_name: &str) -> Vec<Instance> {
+        root.get_descendants()
+            .into_iter()
+            .filter(|inst| inst.get_class_name() == class_name)
+            .collect()
+    }
+
+    #[test]
+    fn class_index_matches_linear_scan() {
+        let mut root = create_tree(5, 3);
+
+        root.build_class_index();
+        for class_name in CLASS_NAMES {
+            assert_eq!(
+                root.get_descendants_of_class(class_name),
+                linear_scan(&root, class_name)
+            );
+        }
+        assert!(root.get_descendants_of_class("Workspace").is_empty());
+
+        // Structural changes must invalidate the index
+        let mut first_model = root.get_descendants_of_class("Model").remove(0);
+        let part = Instance::new_orphaned("Part");
+        part.set_parent(Some(first_model.clone()));
+        assert_eq!(
+            root.get_descendants_of_class("Part"),
+            linear_scan(&root, "Part")
+        );
+        assert!(root.get_descendants_of_class("Part").contains(&part));
+
+        first_model.destroy();
+        for class_name in CLASS_NAMES {
+            assert_eq!(
+                root.get_descendants_of_class(class_name),
+                linear_scan(&root, class_name)
+            );
+        }
+        assert!(!root.get_descendants_of_class("Part").contains(&part));
+
+        root.clear_all_children();
+        for class_name in CLASS_NAMES {
+            assert!(root.get_descendants_of_class(class_name).is_empty());
+        }
+
+        root.destroy();
+    }
+
+    fn is_index_current(root: &Instance) -> bool {
+        let indexes = CLASS_INDEXES.lock().unwrap();
+        indexes[&root.dom_ref].is_current(root.dom_ref)
+    }
+
+    #[test]
+    fn class_index_ignores_unrelated_changes() {
+        let mut root = create_tree(3, 2);
+        let mut other = create_tree(3, 2);
+
+        root.build_class_index();
+        assert!(is_index_current(&root));
+
+        // Changes outside of the indexed tree must keep the index
+        let part = Instance::new_orphaned("Part");
+        part.set_parent(Some(other.clone()));
+        part.clone_instance().set_parent(Some(other.clone()));
+        other.get_descendants_of_class("Model")[0].destroy();
+        root.set_parent(Some(other.clone()));
+        root.set_parent(None);
+        assert!(is_index_current(&root));
+
+        // Changes anywhere below the indexed root must invalidate it
+        let folder = root.get_descendants_of_class("Folder")[0].clone();
+        let part = Instance::new_orphaned("Part");
+        part.set_parent(Some(folder.clone()));
+        assert!(!is_index_current(&root));
+        assert!(root.get_descendants_of_class("Part").contains(&part));
+        assert!(is_index_current(&root));
+
+        part.set_parent(Some(other.clone()));
+        assert!(!is_index_current(&root));
+        assert!(!root.get_descendants_of_class("Part").contains(&part));
+
+        root.destroy();
+        other.destroy();
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture"]
+    fn class_index_benchmark() {
+        const ITERATIONS: usize = 100;
+
+        let mut root = create_tree(8, 4);
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            for class_name in CLASS_NAMES {
+                root.get_descendants_of_class(class_name);
+            }
+        }
+        let linear = start.elapsed();
+
+        let start = Instant::now();
+        root.build_class_index();
+        for _ in 0..ITERATIONS {
+            for class_name in CLASS_NAMES {
+                root.get_descendants_of_class(class_name);
+            }
+        }
+        let indexed = start.elapsed();
+
+        println!("linear scan: {linear:?}, indexed: {indexed:?}");
+
+        root.destroy();
+    }
+}
+
+#[cfg(test)]
+mod validate_model_test {
+    use super::{Instance, ModelIssue};
+
+    #[test]
+    fn validate_model_finds_parent_cycles() {
+        let mut first = Instance::new_orphaned("Folder");
+        let mut second = Instance::new_orphaned("Folder");
+        second.set_parent(Some(first.clone()));
+        assert!(Instance::validate_model(&[first.clone()]).is_empty());
+
+        first.set_parent(Some(second.clone()));
+        assert_eq!(
+            Instance::validate_model(&[first.clone()]),
+            vec![ModelIssue::ParentCycle {
+                instance: first.dom_ref
+            }]
+        );
+        assert_eq!(
+            Instance::validate_model(&[first.clone(), second.clone()]).len(),
+            2
+        );
+
+        // Break the cycle again, it would otherwise live on in the shared dom
+        second.set_parent(None);
+        assert!(first.destroy());
+        assert!(second.destroy());
+    }
+}
@@ -0,0 +1,112 @@
+use glam::{Mat3, Mat4, Vec3};
+use mlua::prelude::*;
+use rbx_dom_weak::types::Variant as DomValue;
+
+use crate::roblox::{
+    datatypes::types::{CFrame, Vector3},
+    shared::{classes::add_class_restricted_method, instance::find_property_info},
+};
+
+use super::Instance;
+
+pub const CLASS_NAME: &str = "Model";
+
+pub fn add_methods<'lua, M: LuaUserDataMethods<'lua, Instance>>(m: &mut M) {
+    add_class_restricted_method(m, CLASS_NAME, "GetBoundingBox", model_get_bounding_box);
+}
+
+/**
+    Gets the orientation + center, and size, of the smallest box that contains
+    all descendant parts of this model, oriented along the pivot of the model.
+
+    ### See Also
+    * [`GetBoundingBox`](https://create.roblox.com/docs/reference/engine/classes/Model#GetBoundingBox)
+    on the Roblox Developer Hub
+*/
+fn model_get_bounding_box(_: &Lua, this: &Instance, _: ()) -> LuaResult<(CFrame, Vector3)> {
+    let orientation = model_pivot_orientation(this);
+    let orientation_inverse = orientation.transpose();
+
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    let mut found = false;
+
+    for part in this.get_descendants() {
+        if !part.is_a("BasePart") {
+            continue;
+        }
+
+        let (cframe, size) = match (
+            get_property_or_default(&part, "CFrame"),
+            get_property_or_default(&part, "Size"),
+        ) {
+            (Some(DomValue::CFrame(cframe)), Some(DomValue::Vector3(size))) => (cframe, size),
+            _ => continue,
+        };
+
+        let cframe = CFrame::from(cframe).0;
+        let half_size = Vector3::from(size).0 / 2.0;
+
+        for corner in BOX_CORNERS {
+            let world = cframe.transform_point3(corner * half_size);
+            let local = orientation_inverse * world;
+            min = min.min(local);
+            max = max.max(local);
+        }
+
+        found = true;
+    }
+
+    if !found {
+        return Ok((CFrame::IDENTITY, Vector3(Vec3::ZERO)));
+    }
+
+    let center = orientation * ((min + max) / 2.0);
+    let cframe = Mat4::from_cols(
+        orientation.x_axis.extend(0.0),
+        orientation.y_axis.extend(0.0),
+        orientation.z_axis.extend(0.0),
+        center.extend(1.0),
+    );
+
+    Ok((CFrame(cframe), Vector3(max - min)))
+}
+
+const BOX_CORNERS: [Vec3; 8] = [
+    Vec3::new(-1.0, -1.0, -1.0),
+    Vec3::new(-1.0, -1.0, 1.0),
+    Vec3::new(-1.0, 1.0, -1.0),
+    Vec3::new(-1.0, 1.0, 1.0),
+    Vec3::new(1.0, -1.0, -1.0),
+    Vec3::new(1.0, -1.0, 1.0),
+    Vec3::new(1.0, 1.0, -1.0),
+    Vec3::new(1.0, 1.0, 1.0),
+];
+
+/**
+    Gets the rotation of the pivot for a model.
+
+    This will use the primary part of the model if one is set, otherwise
+    the world pivot of the model, falling back to the identity rotation.
+*/
+fn model_pivot_orientation(this: &Instance) -> Mat3 {
+    if let Some(DomValue::Ref(primary_ref)) = this.get_property("PrimaryPart") {
+        if let Some(primary) = Instance::new_opt(primary_ref) {
+            if let Some(DomValue::CFrame(cframe)) = get_property_or_default(&primary, "CFrame") {
+                return Mat3::from_mat4(CFrame::from(cframe).0);
+            }
+        }
+    }
+    if let Some(DomValue::OptionalCFrame(Some(cframe))) = this.get_property("WorldPivotData") {
+        return Mat3::from_mat4(CFrame::from(cframe).0);
+    }
+    Mat3::IDENTITY
+}
+
+fn get_property_or_default(instance: &Instance, name: &str) -> Option<DomValue> {
+    instance.get_property(name).or_else(|| {
+        find_property_info(instance.get_class_name(), name)
+            .and_then(|info| info.value_default)
+            .cloned()
+    })
+}
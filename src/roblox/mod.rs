@@ -47,6 +47,10 @@ fn create_all_exports(lua: &Lua) -> LuaResult<Vec<(&'static str, LuaValue)>> {
 }
 
 pub fn module(lua: &Lua) -> LuaResult<LuaTable> {
+    // Datatypes read and write buffers through the builtin library,
+    // which must be captured before any user code can replace it
+    shared::buffer::BufferLibrary::capture(lua)?;
+
     // FUTURE: We can probably create these lazily as users
     // index the main exports (this return value) table and
     // save some memory and startup time. The full exports
@@ -0,0 +1,121 @@
+use glam::Vec3;
+use mlua::prelude::*;
+
+const REGISTRY_KEY: &str = "RobloxBufferLibrary";
+
+/**
+    The builtin `buffer` library, stored in the Lua registry.
+
+    The library is captured once, when the roblox library is created, since
+    the `buffer` global may be replaced by user code at any point after that.
+*/
+pub(crate) struct BufferLibrary;
+
+impl BufferLibrary {
+    /**
+        Captures the builtin `buffer` library for the given Lua state,
+        unless it has already been captured.
+    */
+    pub(crate) fn capture(lua: &Lua) -> LuaResult<()> {
+        if lua
+            .named_registry_value::<Option<LuaTable>>(REGISTRY_KEY)?
+            .is_none()
+        {
+            let buffer_lib = lua.globals().get::<_, LuaTable>("buffer")?;
+            lua.set_named_registry_value(REGISTRY_KEY, buffer_lib)?;
+        }
+        Ok(())
+    }
+
+    /**
+        Gets the builtin `buffer` library for the given Lua state.
+    */
+    pub(crate) fn get(lua: &Lua) -> LuaResult<LuaTable<'_>> {
+        Self::capture(lua)?;
+        lua.named_registry_value(REGISTRY_KEY)
+    }
+}
+
+/**
+    Makes sure that the given value is a buffer.
+
+    Returns the builtin `buffer` library along with the buffer and its length, since
+    there is no direct way to read or write buffer contents from the Rust side.
+*/
+pub(crate) fn ensure_buffer<'lua>(
+    lua: &'lua Lua,
+    buffer: LuaValue<'lua>,
+) -> LuaResult<(LuaTable<'lua>, LuaValue<'lua>, usize)> {
+    if buffer.type_name() != "buffer" {
+        return Err(LuaError::FromLuaConversionError {
+            from: buffer.type_name(),
+            to: "buffer",
+            message: Some(format!("Expected buffer, got {}", buffer.type_name())),
+        });
+    }
+
+    let buffer_lib = BufferLibrary::get(lua)?;
+    let buffer_len = buffer_lib
+        .get::<_, LuaFunction>("len")?
+        .call::<_, usize>(buffer.clone())?;
+
+    Ok((buffer_lib, buffer, buffer_len))
+}
+
+/**
+    The number of bytes that a `Vector3` takes up in a buffer, stored
+    as three consecutive little-endian `f32` values for `x, y, z`.
+*/
+pub(crate) const BUFFER_VECTOR3_SIZE: usize = 12;
+
+/**
+    Makes sure that the given value is a buffer of tightly packed
+    `Vector3`s, and copies its contents out, since copying the whole
+    buffer out once is much cheaper than reading it one float at a time.
+
+    Returns the builtin `buffer` library along with the contents of the buffer.
+*/
+pub(crate) fn ensure_buffer_vector3s<'lua>(
+    lua: &'lua Lua,
+    buffer: LuaValue<'lua>,
+) -> LuaResult<(LuaTable<'lua>, LuaString<'lua>)> {
+    let (buffer_lib, buffer, buffer_len) = ensure_buffer(lua, buffer)?;
+    if buffer_len % BUFFER_VECTOR3_SIZE != 0 {
+        return Err(LuaError::RuntimeError(format!(
+            "Buffer length must be a multiple of {BUFFER_VECTOR3_SIZE} bytes, got {buffer_len}"
+        )));
+    }
+
+    let bytes = buffer_lib
+        .get::<_, LuaFunction>("tostring")?
+        .call::<_, LuaString>(buffer)?;
+
+    Ok((buffer_lib, bytes))
+}
+
+/**
+    Reads tightly packed `Vector3`s from the contents of a buffer.
+
+    Any trailing bytes that do not make up a full `Vector3` are ignored.
+*/
+pub(crate) fn read_vector3s(bytes: &[u8]) -> impl Iterator<Item = Vec3> + '_ {
+    let read_f32 = |b: &[u8]| f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+    bytes.chunks_exact(BUFFER_VECTOR3_SIZE).map(move |chunk| {
+        Vec3::new(
+            read_f32(&chunk[0..4]),
+            read_f32(&chunk[4..8]),
+            read_f32(&chunk[8..12]),
+        )
+    })
+}
+
+/**
+    Writes `Vector3`s tightly packed, as the contents of a buffer.
+*/
+pub(crate) fn write_vector3s(vectors: impl IntoIterator<Item = Vec3>) -> Vec<u8> {
+    vectors
+        .into_iter()
+        .flat_map(|vector| vector.to_array())
+        .flat_map(f32::to_le_bytes)
+        .collect()
+}
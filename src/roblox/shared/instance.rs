@@ -1,7 +1,9 @@
 use std::borrow::{Borrow, BorrowMut, Cow};
 
 use rbx_dom_weak::types::{Variant as DomValue, VariantType as DomType};
-use rbx_reflection::{ClassTag, DataType};
+use rbx_reflection::{
+    ClassTag, DataType, PropertyKind, PropertyMigration, PropertySerialization, Scriptability,
+};
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct PropertyInfo {
@@ -9,6 +11,82 @@ pub(crate) struct PropertyInfo {
     pub enum_default: Option<u32>,
     pub value_type: Option<DomType>,
     pub value_default: Option<&'static DomValue>,
+    pub scriptability: Option<Scriptability>,
+    pub migration: Option<&'static PropertyMigration>,
+}
+
+/**
+    Lua app data that, when set to `false`, allows writing to properties
+    that are read-only or not scriptable in the reflection database.
+
+    Writing to these properties is an error by default, same as during
+    normal scripting in the engine, but tooling that needs to be able to
+    write everything may turn these restrictions off.
+*/
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StrictPropertyWrites(pub bool);
+
+impl Default for StrictPropertyWrites {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/**
+    Checks if the given property may be written to from
+    Lua, according to its scriptability, returning an
+    error message describing the restriction if not.
+
+    Properties with custom scriptability are writable, since they are
+    only restricted by the engine to be modified through other means.
+*/
+pub(crate) fn ensure_property_writable(
+    property_name: impl AsRef<str>,
+    property_info: &PropertyInfo,
+) -> Result<(), String> {
+    let property_name = property_name.as_ref();
+    match property_info.scriptability {
+        Some(Scriptability::Read) => Err(format!(
+            "Failed to set property '{property_name}' - property is read-only"
+        )),
+        Some(Scriptability::None) => Err(format!(
+            "Failed to set property '{property_name}' - property is not scriptable"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/**
+    Finds the canonical name of a property of the given class.
+
+    Some properties have aliases in the reflection database, such as `size`
+    for `Size` or `Color3uint8` for `Color`, which usually come from the names
+    used in serialized files. These aliases all resolve to the canonical
+    property, which is the one that is actually stored on instances.
+
+    Returns the given property name if it is not an alias.
+*/
+pub(crate) fn find_canonical_property_name(
+    instance_class: impl AsRef<str>,
+    property_name: &str,
+) -> &str {
+    let db = rbx_reflection_database::get();
+
+    let mut class_name = instance_class.as_ref();
+    while let Some(class) = db.classes.get(class_name) {
+        if let Some(prop_definition) = class.properties.get(property_name) {
+            if let PropertyKind::Alias { alias_for } = &prop_definition.kind {
+                return alias_for.as_ref();
+            }
+            break;
+        } else if let Some(sup) = &class.superclass {
+            class_name = sup;
+        } else {
+            break;
+        }
+    }
+
+    property_name
 }
 
 /**
@@ -51,16 +129,31 @@ pub(crate) fn find_property_info(
                 base class but the default value can be part of
                 some separate class, it will be checked below
             */
+            let scriptability = Some(prop_definition.scriptability);
+            let migration = match &prop_definition.kind {
+                PropertyKind::Canonical {
+                    serialization: PropertySerialization::Migrate(migration),
+                } => Some(migration),
+                _ => None,
+            };
             class_info = Some(match &prop_definition.data_type {
                 DataType::Enum(enum_name) => PropertyInfo {
                     enum_name: Some(Cow::Borrowed(enum_name)),
+                    scriptability,
+                    migration,
                     ..Default::default()
                 },
                 DataType::Value(value_type) => PropertyInfo {
                     value_type: Some(*value_type),
+                    scriptability,
+                    migration,
+                    ..Default::default()
+                },
+                _ => PropertyInfo {
+                    scriptability,
+                    migration,
                     ..Default::default()
                 },
-                _ => Default::default(),
             });
             break;
         } else if let Some(sup) = &class.superclass {
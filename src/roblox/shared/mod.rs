@@ -1,3 +1,5 @@
+pub(crate) mod buffer;
 pub(crate) mod classes;
 pub(crate) mod instance;
+pub(crate) mod random;
 pub(crate) mod userdata;
@@ -0,0 +1,51 @@
+use mlua::prelude::*;
+use rand::Rng;
+
+/**
+    A small seedable random number generator, using the
+    [SplitMix64](https://prng.di.unimi.it/splitmix64.c) algorithm.
+
+    This is used for seeded random datatypes since, unlike the generators
+    in the `rand` crate, it is guaranteed to never change its output.
+*/
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /**
+        Creates a new generator from an optional seed given from Lua.
+
+        The seed must be an integer if given, otherwise
+        a random seed will be picked from the thread rng.
+    */
+    pub fn from_lua_seed(seed: Option<f64>) -> LuaResult<Self> {
+        match seed {
+            Some(seed) if seed.fract() != 0.0 || !seed.is_finite() => Err(LuaError::RuntimeError(
+                format!("Random seed must be an integer, got {seed}"),
+            )),
+            Some(seed) => Ok(Self::new(seed as i64 as u64)),
+            None => Ok(Self::new(rand::thread_rng().gen())),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /**
+        Gets the next random number in the range `[0, 1)`.
+    */
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
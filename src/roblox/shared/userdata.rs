@@ -1,7 +1,9 @@
-use std::{any::type_name, cell::RefCell, fmt, ops};
+use std::{cell::RefCell, fmt, ops};
 
 use mlua::prelude::*;
 
+use crate::roblox::exports::LuaExportsTable;
+
 // Utility functions
 
 type ListWriter = dyn Fn(&mut fmt::Formatter<'_>, bool, &str) -> fmt::Result;
@@ -19,6 +21,116 @@ pub fn make_list_writer() -> Box<ListWriter> {
     })
 }
 
+/**
+    Converts the arguments given to a datatype constructor with a single fixed signature,
+    such as `Ray.new`, erroring with the expected amount of arguments if the amount is wrong.
+
+    Without this check, missing arguments would be converted from `nil` and extra
+    arguments would be silently ignored, neither of which gives a helpful error.
+*/
+pub fn constructor_args<'lua, A: FromLuaMulti<'lua>>(
+    lua: &'lua Lua,
+    constructor: &'static str,
+    arity: ops::RangeInclusive<usize>,
+    args: LuaMultiValue<'lua>,
+) -> LuaResult<A> {
+    let count = args.len();
+    if !arity.contains(&count) {
+        let (min, max) = (*arity.start(), *arity.end());
+        let expected = if min == max {
+            format!("{min}")
+        } else {
+            format!("{min} to {max}")
+        };
+        let plural = if max == 1 { "argument" } else { "arguments" };
+        return Err(LuaError::RuntimeError(format!(
+            "{constructor} expects {expected} {plural}, got {count}"
+        )));
+    }
+    A::from_lua_multi(args, lua)
+}
+
+/**
+    The maximum amount of keypoints in a `ColorSequence` or `NumberSequence`, same as in Roblox.
+*/
+pub const MAX_SEQUENCE_KEYPOINTS: usize = 20;
+
+/**
+    Makes sure that a sequence datatype has no more than [`MAX_SEQUENCE_KEYPOINTS`] keypoints.
+*/
+pub fn ensure_sequence_keypoint_count(datatype: &'static str, count: usize) -> LuaResult<()> {
+    if count > MAX_SEQUENCE_KEYPOINTS {
+        Err(LuaError::RuntimeError(format!(
+            "{datatype} can have at most {MAX_SEQUENCE_KEYPOINTS} keypoints, got {count}"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+// Int16 datatype overflow behavior
+
+/**
+    How arithmetic on datatypes backed by 16-bit integers, such as
+    `Vector2int16` and `Vector3int16`, handles overflowing results.
+
+    This is stored as Lua app data, and defaults to wrapping
+    around on overflow, which is the same behavior as in Roblox.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Int16Overflow {
+    #[default]
+    Wrap,
+    Saturate,
+}
+
+impl Int16Overflow {
+    /**
+        Gets the current overflow behavior for the given Lua state.
+    */
+    pub fn get(lua: &Lua) -> Self {
+        lua.app_data_ref::<Self>()
+            .map(|mode| *mode)
+            .unwrap_or_default()
+    }
+
+    /**
+        Sets the overflow behavior for the given Lua state.
+    */
+    pub fn set(lua: &Lua, mode: Self) {
+        lua.set_app_data(mode);
+    }
+
+    /**
+        Converts the result of an arithmetic operation to the 16-bit integer range.
+    */
+    pub fn apply(self, value: i32) -> i32 {
+        match self {
+            Self::Wrap => value as i16 as i32,
+            Self::Saturate => value.clamp(i16::MIN as i32, i16::MAX as i32),
+        }
+    }
+}
+
+impl<'lua> FromLua<'lua> for Int16Overflow {
+    fn from_lua(value: LuaValue<'lua>, _: &'lua Lua) -> LuaResult<Self> {
+        match &value {
+            LuaValue::String(s) => match s.to_str()? {
+                "Wrap" => Ok(Self::Wrap),
+                "Saturate" => Ok(Self::Saturate),
+                other => Err(LuaError::RuntimeError(format!(
+                    "Invalid int16 overflow mode '{other}' - expected Wrap or Saturate"
+                ))),
+            },
+            _ => Err(LuaError::FromLuaConversionError {
+                from: value.type_name(),
+                to: "Int16Overflow",
+                message: Some(format!("Expected string, got {}", value.type_name())),
+            }),
+        }
+    }
+}
+
 // Userdata metamethod implementations
 
 pub fn userdata_impl_to_string<D>(_: &Lua, datatype: &D, _: ()) -> LuaResult<String>
@@ -66,7 +178,12 @@ where
 
 pub fn userdata_impl_mul_f32<D>(_: &Lua, datatype: &D, rhs: LuaValue) -> LuaResult<D>
 where
-    D: LuaUserData + ops::Mul<D, Output = D> + ops::Mul<f32, Output = D> + Copy + 'static,
+    D: LuaUserData
+        + for<'lua> LuaExportsTable<'lua>
+        + ops::Mul<D, Output = D>
+        + ops::Mul<f32, Output = D>
+        + Copy
+        + 'static,
 {
     match &rhs {
         LuaValue::Number(n) => return Ok(*datatype * *n as f32),
@@ -80,10 +197,10 @@ where
     };
     Err(LuaError::FromLuaConversionError {
         from: rhs.type_name(),
-        to: type_name::<D>(),
+        to: D::EXPORT_NAME,
         message: Some(format!(
             "Expected {} or number, got {}",
-            type_name::<D>(),
+            D::EXPORT_NAME,
             rhs.type_name()
         )),
     })
@@ -91,7 +208,12 @@ where
 
 pub fn userdata_impl_mul_i32<D>(_: &Lua, datatype: &D, rhs: LuaValue) -> LuaResult<D>
 where
-    D: LuaUserData + ops::Mul<D, Output = D> + ops::Mul<i32, Output = D> + Copy + 'static,
+    D: LuaUserData
+        + for<'lua> LuaExportsTable<'lua>
+        + ops::Mul<D, Output = D>
+        + ops::Mul<i32, Output = D>
+        + Copy
+        + 'static,
 {
     match &rhs {
         LuaValue::Number(n) => return Ok(*datatype * *n as i32),
@@ -105,10 +227,10 @@ where
     };
     Err(LuaError::FromLuaConversionError {
         from: rhs.type_name(),
-        to: type_name::<D>(),
+        to: D::EXPORT_NAME,
         message: Some(format!(
             "Expected {} or number, got {}",
-            type_name::<D>(),
+            D::EXPORT_NAME,
             rhs.type_name()
         )),
     })
@@ -116,7 +238,12 @@ where
 
 pub fn userdata_impl_div_f32<D>(_: &Lua, datatype: &D, rhs: LuaValue) -> LuaResult<D>
 where
-    D: LuaUserData + ops::Div<D, Output = D> + ops::Div<f32, Output = D> + Copy + 'static,
+    D: LuaUserData
+        + for<'lua> LuaExportsTable<'lua>
+        + ops::Div<D, Output = D>
+        + ops::Div<f32, Output = D>
+        + Copy
+        + 'static,
 {
     match &rhs {
         LuaValue::Number(n) => return Ok(*datatype / *n as f32),
@@ -130,10 +257,10 @@ where
     };
     Err(LuaError::FromLuaConversionError {
         from: rhs.type_name(),
-        to: type_name::<D>(),
+        to: D::EXPORT_NAME,
         message: Some(format!(
             "Expected {} or number, got {}",
-            type_name::<D>(),
+            D::EXPORT_NAME,
             rhs.type_name()
         )),
     })
@@ -141,7 +268,12 @@ where
 
 pub fn userdata_impl_div_i32<D>(_: &Lua, datatype: &D, rhs: LuaValue) -> LuaResult<D>
 where
-    D: LuaUserData + ops::Div<D, Output = D> + ops::Div<i32, Output = D> + Copy + 'static,
+    D: LuaUserData
+        + for<'lua> LuaExportsTable<'lua>
+        + ops::Div<D, Output = D>
+        + ops::Div<i32, Output = D>
+        + Copy
+        + 'static,
 {
     match &rhs {
         LuaValue::Number(n) => return Ok(*datatype / *n as i32),
@@ -155,10 +287,10 @@ where
     };
     Err(LuaError::FromLuaConversionError {
         from: rhs.type_name(),
-        to: type_name::<D>(),
+        to: D::EXPORT_NAME,
         message: Some(format!(
             "Expected {} or number, got {}",
-            type_name::<D>(),
+            D::EXPORT_NAME,
             rhs.type_name()
         )),
     })
@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+use tokio::fs;
+
+use super::metadata::{Metadata, FORMAT_VERSION};
+
+/**
+    A manifest describing a standalone binary produced by `lune build`.
+
+    This is written as JSON next to the binary, so that build pipelines can
+    keep track of artifacts without having to inspect the binaries themselves.
+
+    Checksums are hex-encoded BLAKE3 hashes, for the full binary as
+    well as for the bytecode of each module embedded in it.
+*/
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub lune_version: String,
+    pub target: String,
+    pub format_version: u8,
+    pub compression: Option<String>,
+    pub modules: Vec<ManifestModule>,
+    pub size: usize,
+    pub checksum: String,
+}
+
+/**
+    A single module embedded in a standalone binary, as described by its [`Manifest`].
+
+    The main script always comes first, followed by any bundled modules.
+*/
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestModule {
+    pub name: String,
+    pub size: usize,
+    pub checksum: String,
+}
+
+impl Manifest {
+    /**
+        Creates a manifest for the given patched standalone binary, built for the given target.
+
+        The embedded modules are read back out of the binary, so that the manifest
+        always describes what the binary actually contains, and not just its inputs.
+    */
+    pub fn from_patched_bin(target: impl Into<String>, patched_bin: &[u8]) -> Result<Self> {
+        let meta = Metadata::from_bytes(patched_bin)?;
+        Ok(Self {
+            lune_version: env!("CARGO_PKG_VERSION").to_string(),
+            target: target.into(),
+            format_version: FORMAT_VERSION,
+            compression: None,
+            modules: [(&meta.script_name, &meta.bytecode)]
+                .into_iter()
+                .chain(&meta.modules)
+                .map(|(name, bytecode)| ManifestModule {
+                    name: name.clone(),
+                    size: bytecode.len(),
+                    checksum: blake3::hash(bytecode).to_hex().to_string(),
+                })
+                .collect(),
+            size: patched_bin.len(),
+            checksum: blake3::hash(patched_bin).to_hex().to_string(),
+        })
+    }
+
+    /**
+        Gets the path that the manifest for a binary at the given path should be written to.
+    */
+    pub fn path_for(binary_path: impl AsRef<Path>) -> PathBuf {
+        let binary_path = binary_path.as_ref();
+        let mut file_name = binary_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".manifest.json");
+        binary_path.with_file_name(file_name)
+    }
+
+    /**
+        Writes the manifest as pretty-printed JSON to the given path.
+    */
+    pub async fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        fs::write(path, contents).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use super::{Manifest, Metadata, FORMAT_VERSION};
+
+    #[test]
+    fn fields_match_build_inputs() {
+        let meta = Metadata::compile("scripts/main", "print('hello')");
+
+        let mut bin = b"base executable contents".to_vec();
+        bin.extend_from_slice(&meta.to_bytes());
+
+        let manifest = Manifest::from_patched_bin("linux-x86_64", &bin).unwrap();
+        assert_eq!(manifest.lune_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.target, "linux-x86_64");
+        assert_eq!(manifest.format_version, FORMAT_VERSION);
+        assert_eq!(manifest.compression, None);
+        assert_eq!(manifest.size, bin.len());
+        assert_eq!(manifest.checksum, blake3::hash(&bin).to_hex().as_str());
+
+        assert_eq!(manifest.modules.len(), 1);
+        let module = &manifest.modules[0];
+        assert_eq!(module.name, "scripts/main");
+        assert_eq!(module.size, meta.bytecode.len());
+        assert_eq!(
+            module.checksum,
+            blake3::hash(&meta.bytecode).to_hex().as_str()
+        );
+    }
+
+    #[test]
+    fn lists_bundled_modules_after_script() {
+        let meta =
+            Metadata::compile("main", "return require(\"./lib\")").with_modules(BTreeMap::from([
+                ("lib.luau".to_string(), b"return 1".to_vec()),
+            ]));
+        let manifest = Manifest::from_patched_bin("linux-x86_64", &meta.to_bytes()).unwrap();
+
+        let names = manifest
+            .modules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["main", "lib.luau"]);
+        assert_eq!(manifest.modules[1].size, meta.modules["lib.luau"].len());
+    }
+
+    #[test]
+    fn serializes_camel_case_keys() {
+        let bin = Metadata::compile("main", "return 1").to_bytes();
+        let manifest = Manifest::from_patched_bin("windows-x86_64", &bin).unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["luneVersion"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["target"], "windows-x86_64");
+        assert_eq!(json["formatVersion"], u64::from(FORMAT_VERSION));
+        assert!(json["compression"].is_null());
+        assert_eq!(json["modules"][0]["name"], "main");
+        assert_eq!(json["size"], bin.len());
+    }
+
+    #[test]
+    fn path_is_next_to_binary() {
+        assert_eq!(
+            Manifest::path_for("out/game.exe"),
+            PathBuf::from("out/game.exe.manifest.json")
+        );
+        assert_eq!(
+            Manifest::path_for("game"),
+            PathBuf::from("game.manifest.json")
+        );
+    }
+
+    #[test]
+    fn rejects_non_standalone_binaries() {
+        assert!(Manifest::from_patched_bin("linux-x86_64", b"not standalone").is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{env, path::PathBuf};
+use std::{collections::BTreeMap, env, path::PathBuf};
 
 use anyhow::{bail, Result};
 use mlua::Compiler as LuaCompiler;
@@ -9,27 +9,32 @@ pub const CURRENT_EXE: Lazy<PathBuf> =
     Lazy::new(|| env::current_exe().expect("failed to get current exe"));
 const MAGIC: &[u8; 8] = b"cr3sc3nt";
 
-/*
-    TODO: Right now all we do is append the bytecode to the end
-    of the binary, but we will need a more flexible solution in
-    the future to store many files as well as their metadata.
-
-    The best solution here is most likely to use a well-supported
-    and rust-native binary serialization format with a stable
-    specification, one that also supports byte arrays well without
-    overhead, so the best solution seems to currently be Postcard:
+/**
+    Version of the standalone binary format, stored in the footer right before the magic bytes.
 
-    https://github.com/jamesmunns/postcard
-    https://crates.io/crates/postcard
+    This must be bumped whenever the layout of the metadata chunk changes, so
+    that runtimes can refuse to load binaries written in a format they do not know.
 */
+pub const FORMAT_VERSION: u8 = 2;
 
 /**
     Metadata for a standalone Lune executable. Can be used to
     discover and load the bytecode contained in a standalone binary.
+
+    The script name is the file stem of the script that was compiled, and is
+    used as the chunk name when loading the bytecode, so that errors and stack
+    traces point to the original file instead of an opaque name, without
+    embedding any paths from the machine that the binary was built on.
+
+    Modules are any other scripts that were bundled alongside the main script,
+    keyed by their path relative to the main script, which is also used as their
+    chunk name. Relative requires are resolved against these paths at runtime.
 */
 #[derive(Debug, Clone)]
 pub struct Metadata {
+    pub script_name: String,
     pub bytecode: Vec<u8>,
+    pub modules: BTreeMap<String, Vec<u8>>,
 }
 
 impl Metadata {
@@ -46,47 +51,92 @@ impl Metadata {
     }
 
     /**
-        Creates a patched standalone binary from the given script contents.
+        Creates a patched standalone binary from the given script name and contents,
+        as well as the contents of any modules that should be bundled alongside it.
     */
     pub async fn create_env_patched_bin(
         base_exe_path: PathBuf,
+        script_name: impl Into<String>,
         script_contents: impl Into<Vec<u8>>,
+        modules: BTreeMap<String, Vec<u8>>,
     ) -> Result<Vec<u8>> {
-        let compiler = LuaCompiler::new()
-            .set_optimization_level(2)
-            .set_coverage_level(0)
-            .set_debug_level(1);
-
         let mut patched_bin = fs::read(base_exe_path).await?;
 
         // Compile luau input into bytecode
-        let bytecode = compiler.compile(script_contents.into());
+        let meta = Self::compile(script_name, script_contents).with_modules(modules);
 
         // Append the bytecode / metadata to the end
-        let meta = Self { bytecode };
         patched_bin.extend_from_slice(&meta.to_bytes());
 
         Ok(patched_bin)
     }
 
+    /**
+        Compiles the given script contents into bytecode, keeping the script name.
+    */
+    pub fn compile(script_name: impl Into<String>, script_contents: impl Into<Vec<u8>>) -> Self {
+        Self {
+            script_name: script_name.into(),
+            bytecode: compiler().compile(script_contents.into()),
+            modules: BTreeMap::new(),
+        }
+    }
+
+    /**
+        Compiles the given module contents into bytecode, and bundles them alongside the script.
+    */
+    #[must_use]
+    pub fn with_modules(mut self, modules: BTreeMap<String, Vec<u8>>) -> Self {
+        let compiler = compiler();
+        for (name, contents) in modules {
+            self.modules.insert(name, compiler.compile(contents));
+        }
+        self
+    }
+
     /**
         Tries to read a standalone binary from the given bytes.
     */
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
         let bytes = bytes.as_ref();
-        if bytes.len() < 16 || !bytes.ends_with(MAGIC) {
+        if bytes.len() < FOOTER_SIZE || !bytes.ends_with(MAGIC) {
             bail!("not a standalone binary")
         }
 
-        // Extract bytecode size
-        let bytecode_size_bytes = &bytes[bytes.len() - 16..bytes.len() - 8];
-        let bytecode_size =
-            usize::try_from(u64::from_be_bytes(bytecode_size_bytes.try_into().unwrap()))?;
+        // Make sure we know how to read this version of the format
+        let footer = &bytes[bytes.len() - FOOTER_SIZE..];
+        let version = footer[8];
+        if version != FORMAT_VERSION {
+            bail!(
+                "standalone binary uses format version {version}, but this runtime only \
+                 supports version {FORMAT_VERSION} - rebuild it with a matching version of Lune"
+            )
+        }
 
-        // Extract bytecode
-        let bytecode = bytes[bytes.len() - 16 - bytecode_size..].to_vec();
+        // Extract the metadata chunk right before the footer
+        let chunk_size = usize::try_from(u64::from_be_bytes(footer[..8].try_into()?))?;
+        let contents = &bytes[..bytes.len() - FOOTER_SIZE];
+        let Some(mut chunk) = contents
+            .len()
+            .checked_sub(chunk_size)
+            .map(|start| &contents[start..])
+        else {
+            bail!("standalone binary metadata is corrupt")
+        };
 
-        Ok(Self { bytecode })
+        // Extract the main script, followed by any bundled modules
+        let (script_name, bytecode) = read_entry(&mut chunk)?;
+        let mut modules = BTreeMap::new();
+        while !chunk.is_empty() {
+            let (name, bytecode) = read_entry(&mut chunk)?;
+            modules.insert(name, bytecode);
+        }
+
+        Ok(Self {
+            script_name,
+            bytecode,
+            modules,
+        })
     }
 
     /**
@@ -94,9 +144,188 @@ impl Metadata {
     */
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
-        bytes.extend_from_slice(&self.bytecode);
-        bytes.extend_from_slice(&(self.bytecode.len() as u64).to_be_bytes());
+        write_entry(&mut bytes, &self.script_name, &self.bytecode);
+        for (name, bytecode) in &self.modules {
+            write_entry(&mut bytes, name, bytecode);
+        }
+        bytes.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        bytes.push(FORMAT_VERSION);
         bytes.extend_from_slice(MAGIC);
         bytes
     }
 }
+
+/**
+    Size of the footer at the very end of a standalone binary, which
+    contains the metadata chunk size, the format version, and the magic bytes.
+*/
+const FOOTER_SIZE: usize = 8 + 1 + MAGIC.len();
+
+fn compiler() -> LuaCompiler {
+    LuaCompiler::new()
+        .set_optimization_level(2)
+        .set_coverage_level(0)
+        .set_debug_level(1)
+}
+
+/*
+    Each entry in the metadata chunk is a name followed by its bytecode,
+    both prefixed with their size, so that entries can be read in order.
+*/
+
+fn write_entry(bytes: &mut Vec<u8>, name: &str, bytecode: &[u8]) {
+    bytes.extend_from_slice(&(name.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(&(bytecode.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(bytecode);
+}
+
+fn read_entry(chunk: &mut &[u8]) -> Result<(String, Vec<u8>)> {
+    let name = read_sized(chunk)?;
+    let bytecode = read_sized(chunk)?;
+    Ok((String::from_utf8(name.to_vec())?, bytecode.to_vec()))
+}
+
+fn read_sized<'a>(chunk: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let Some((size, rest)) = chunk.split_first_chunk::<8>() else {
+        bail!("standalone binary metadata is corrupt")
+    };
+    let size = usize::try_from(u64::from_be_bytes(*size))?;
+    if size > rest.len() {
+        bail!("standalone binary metadata is corrupt")
+    }
+    let (contents, rest) = rest.split_at(size);
+    *chunk = rest;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, process::ExitCode};
+
+    use lune::Runtime;
+    use mlua::Lua;
+
+    use super::{Metadata, FOOTER_SIZE, FORMAT_VERSION, MAGIC};
+
+    #[test]
+    fn round_trip() {
+        let meta = Metadata::compile("scripts/main", "return 1 + 2");
+
+        let mut bin = b"base executable contents".to_vec();
+        bin.extend_from_slice(&meta.to_bytes());
+
+        let read = Metadata::from_bytes(bin).unwrap();
+        assert_eq!(read.script_name, "scripts/main");
+        assert_eq!(read.bytecode, meta.bytecode);
+        assert!(read.modules.is_empty());
+    }
+
+    #[test]
+    fn round_trip_with_modules() {
+        let meta = Metadata::compile("main", "return require(\"./lib/util\")").with_modules(
+            BTreeMap::from([
+                ("lib/util.luau".to_string(), b"return 1".to_vec()),
+                ("../shared/init.luau".to_string(), b"return 2".to_vec()),
+            ]),
+        );
+
+        let read = Metadata::from_bytes(meta.to_bytes()).unwrap();
+        assert_eq!(read.script_name, "main");
+        assert_eq!(read.bytecode, meta.bytecode);
+        assert_eq!(read.modules, meta.modules);
+        assert_eq!(read.modules.len(), 2);
+    }
+
+    #[test]
+    fn footer_has_current_version() {
+        let bytes = Metadata::compile("scripts/main", "return 1").to_bytes();
+
+        let footer = &bytes[bytes.len() - FOOTER_SIZE..];
+        assert_eq!(footer[8], FORMAT_VERSION);
+        assert_eq!(&footer[9..], MAGIC);
+        assert!(Metadata::from_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut bytes = Metadata::compile("scripts/main", "return 1").to_bytes();
+
+        let version_index = bytes.len() - MAGIC.len() - 1;
+        bytes[version_index] = FORMAT_VERSION + 1;
+
+        let message = Metadata::from_bytes(&bytes).unwrap_err().to_string();
+        assert!(
+            message.contains(&format!("format version {}", FORMAT_VERSION + 1)),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn errors_report_script_name() {
+        let meta = Metadata::compile("scripts/main", "local x = 1\nerror(\"oops\")");
+        let read = Metadata::from_bytes(meta.to_bytes()).unwrap();
+
+        let lua = Lua::new();
+        let err = lua
+            .load(read.bytecode)
+            .set_name(read.script_name)
+            .exec()
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert!(
+            message.contains("[string \"scripts/main\"]:2:"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn bundled_module_errors_report_module_path() -> anyhow::Result<ExitCode> {
+        let meta = Metadata::compile(
+            "main",
+            r#"
+                local message = require("./lib/fail")
+                assert(
+                    string.find(message, '[string "lib/fail.luau"]:2: oops', 1, true),
+                    "Error should report the original module path and line, got: " .. message
+                )
+            "#,
+        )
+        .with_modules(BTreeMap::from([(
+            "lib/fail.luau".to_string(),
+            b"local ok, err = pcall(function()\n\terror(\"oops\")\nend)\nreturn err".to_vec(),
+        )]));
+        let read = Metadata::from_bytes(meta.to_bytes())?;
+
+        // The bundled module does not exist on disk, so this
+        // will only succeed if it is loaded from the bundle
+        let exit_code = Runtime::new()
+            .with_bundle(std::env::temp_dir(), read.modules)
+            .run(read.script_name, read.bytecode)
+            .await?;
+        Ok(exit_code)
+    }
+
+    #[test]
+    fn rejects_truncated_metadata() {
+        let meta = Metadata::compile("main", "return 1").with_modules(BTreeMap::from([(
+            "lib.luau".to_string(),
+            b"return 2".to_vec(),
+        )]));
+        let mut bytes = meta.to_bytes();
+
+        // Pretend that the chunk is larger than the entries it contains
+        let size_index = bytes.len() - FOOTER_SIZE;
+        let size = u64::from_be_bytes(bytes[size_index..size_index + 8].try_into().unwrap());
+        bytes.splice(size_index..size_index + 8, (size - 1).to_be_bytes());
+
+        assert!(Metadata::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_bytes() {
+        assert!(Metadata::from_bytes(b"not a standalone binary").is_err());
+        assert!(Metadata::from_bytes(b"cr3sc3nt").is_err());
+    }
+}
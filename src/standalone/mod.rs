@@ -1,12 +1,13 @@
-use std::{env, process::ExitCode};
+use std::{env, path::Path, process::ExitCode};
 
 use anyhow::Result;
 use lune::Runtime;
 
+pub(crate) mod manifest;
 pub(crate) mod metadata;
 pub(crate) mod tracer;
 
-use self::metadata::Metadata;
+use self::metadata::{Metadata, CURRENT_EXE};
 
 /**
     Returns whether or not the currently executing Lune binary
@@ -27,11 +28,19 @@ pub async fn check() -> Option<Vec<u8>> {
 pub async fn run(patched_bin: impl AsRef<[u8]>) -> Result<ExitCode> {
     // The first argument is the path to the current executable
     let args = env::args().skip(1).collect::<Vec<_>>();
-    let meta = Metadata::from_bytes(patched_bin).expect("must be a standalone binary");
+    let meta = Metadata::from_bytes(patched_bin)?;
+
+    // Relative paths were resolved against the main script when building,
+    // so anything that was not bundled is looked up next to the binary
+    let root = CURRENT_EXE
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
 
     let result = Runtime::new()
         .with_args(args)
-        .run("STANDALONE", meta.bytecode)
+        .with_bundle(root, meta.modules)
+        .run(meta.script_name, meta.bytecode)
         .await;
 
     Ok(match result {
@@ -1,14 +1,189 @@
-/*
-    TODO: Implement tracing of requires here
-
-    Rough steps / outline:
-
-    1. Create a new tracer struct using a main entrypoint script path
-    2. Some kind of discovery mechanism that goes through all require chains (failing on recursive ones)
-       2a. Conversion of script-relative paths to cwd-relative paths + normalization
-       2b. Cache all found files in a map of file path -> file contents
-       2c. Prepend some kind of symbol to paths that can tell our runtime `require` function that it
-           should look up a bundled/standalone script, a good symbol here is probably a dollar sign ($)
-    3. ???
-    4. Profit
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use tokio::fs;
+
+/**
+    Traces all of the modules that the given main script requires, either
+    directly or through other modules, so that they can be bundled into
+    a standalone binary and loaded without the original files.
+
+    Only relative requires with a string literal path can be traced - aliases, builtins
+    and dynamic paths are left alone, as are relative paths that do not point to an
+    existing file, and these will be resolved next to the standalone binary at runtime.
+
+    Returns the contents of each module, keyed by its path relative to the main script.
+*/
+pub async fn trace_requires(
+    script_path: impl AsRef<Path>,
+    script_contents: impl AsRef<[u8]>,
+) -> Result<BTreeMap<String, Vec<u8>>> {
+    let root = script_path.as_ref().parent().unwrap_or(Path::new(""));
+
+    let mut modules = BTreeMap::new();
+    let mut pending = vec![(PathBuf::new(), script_contents.as_ref().to_vec())];
+
+    while let Some((dir, contents)) = pending.pop() {
+        for path in find_requires(&String::from_utf8_lossy(&contents)) {
+            // Resolve the same candidate paths, in the same order, as `require` does
+            let rel_path = path_clean::clean(dir.join(path));
+            for candidate in candidate_paths(&rel_path) {
+                let key = module_key(&candidate);
+                if modules.contains_key(&key) {
+                    break;
+                }
+
+                let file_path = root.join(&candidate);
+                let is_file = fs::metadata(&file_path)
+                    .await
+                    .is_ok_and(|meta| meta.is_file());
+                if !is_file {
+                    continue;
+                }
+
+                let module_contents = fs::read(&file_path)
+                    .await
+                    .with_context(|| format!("failed to read module {}", file_path.display()))?;
+                let module_dir = candidate.parent().map(Path::to_path_buf);
+
+                modules.insert(key, module_contents.clone());
+                pending.push((module_dir.unwrap_or_default(), module_contents));
+                break;
+            }
+        }
+    }
+
+    Ok(modules)
+}
+
+/**
+    Gets the key of a bundled module from its path, which uses `/` as
+    the separator so that binaries can be built for other platforms.
 */
+fn module_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn candidate_paths(path: &Path) -> [PathBuf; 5] {
+    let with_extension = |path: &Path, ext: &str| {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(ext);
+        path.with_file_name(file_name)
+    };
+    let init = path.join("init");
+    [
+        path.to_path_buf(),
+        with_extension(path, "luau"),
+        with_extension(path, "lua"),
+        with_extension(&init, "luau"),
+        with_extension(&init, "lua"),
+    ]
+}
+
+/**
+    Finds all relative paths given as string literals to `require` in the given source code.
+
+    This is a plain text search and not a full parse, so requires inside
+    of comments or strings are also found, which is fine for bundling.
+*/
+fn find_requires(source: &str) -> Vec<&str> {
+    const KEYWORD: &str = "require";
+
+    let is_ident = |c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | ':');
+
+    let mut paths = Vec::new();
+    for (index, _) in source.match_indices(KEYWORD) {
+        if source[..index].chars().next_back().is_some_and(is_ident) {
+            continue;
+        }
+
+        // Both `require("path")` and `require "path"` are valid calls
+        let rest = source[index + KEYWORD.len()..].trim_start();
+        let rest = rest.strip_prefix('(').unwrap_or(rest).trim_start();
+        let Some(quote) = rest
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\'' | '`'))
+        else {
+            continue;
+        };
+
+        let rest = &rest[1..];
+        let Some(path) = rest.find(quote).map(|end| &rest[..end]) else {
+            continue;
+        };
+        if path.contains(['\\', '\n', '{']) {
+            continue;
+        }
+
+        if path.starts_with("./") || path.starts_with("../") {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{candidate_paths, find_requires, module_key};
+
+    #[test]
+    fn finds_relative_requires() {
+        let source = r#"
+            local a = require("./a")
+            local b = require "../b"
+            local c = require('./c/init')
+            local d = require(`./d`)
+            local e = require ( "./e" )
+        "#;
+        assert_eq!(
+            find_requires(source),
+            ["./a", "../b", "./c/init", "./d", "./e"]
+        );
+    }
+
+    #[test]
+    fn ignores_other_requires() {
+        let source = r#"
+            local fs = require("@lune/fs")
+            local alias = require("@modules/thing")
+            local dynamic = require(path)
+            local method = thing:require("./not")
+            local field = thing.require("./not")
+            local named = myrequire("./not")
+            local interpolated = require(`./{name}`)
+            local absolute = require("/abs/path")
+        "#;
+        assert!(find_requires(source).is_empty());
+    }
+
+    #[test]
+    fn candidates_match_require_order() {
+        let candidates = candidate_paths(Path::new("lib/util"));
+        assert_eq!(
+            candidates,
+            [
+                PathBuf::from("lib/util"),
+                PathBuf::from("lib/util.luau"),
+                PathBuf::from("lib/util.lua"),
+                PathBuf::from("lib/util/init.luau"),
+                PathBuf::from("lib/util/init.lua"),
+            ]
+        );
+    }
+
+    #[test]
+    fn module_keys_use_forward_slashes() {
+        assert_eq!(module_key(Path::new("lib/util.luau")), "lib/util.luau");
+        assert_eq!(
+            module_key(Path::new("../shared/init.luau")),
+            "../shared/init.luau"
+        );
+    }
+}
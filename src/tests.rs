@@ -154,8 +154,12 @@ create_tests! {
 
     roblox_files_deserialize_model: "roblox/files/deserializeModel",
     roblox_files_deserialize_place: "roblox/files/deserializePlace",
+    roblox_files_deserialize_reflection_miss: "roblox/files/deserializeReflectionMiss",
+    roblox_files_merge_into: "roblox/files/mergeInto",
+    roblox_files_model_stats: "roblox/files/modelStats",
     roblox_files_serialize_model: "roblox/files/serializeModel",
     roblox_files_serialize_place: "roblox/files/serializePlace",
+    roblox_files_validate_model: "roblox/files/validateModel",
 
     roblox_instance_attributes: "roblox/instance/attributes",
     roblox_instance_new: "roblox/instance/new",
@@ -163,6 +167,8 @@ create_tests! {
     roblox_instance_tags: "roblox/instance/tags",
 
     roblox_instance_classes_data_model: "roblox/instance/classes/DataModel",
+    roblox_instance_classes_lua_source_container: "roblox/instance/classes/LuaSourceContainer",
+    roblox_instance_classes_model: "roblox/instance/classes/Model",
     roblox_instance_classes_workspace: "roblox/instance/classes/Workspace",
     roblox_instance_classes_terrain: "roblox/instance/classes/Terrain",
 
@@ -173,6 +179,7 @@ create_tests! {
     roblox_instance_methods_clear_all_children: "roblox/instance/methods/ClearAllChildren",
     roblox_instance_methods_clone: "roblox/instance/methods/Clone",
     roblox_instance_methods_destroy: "roblox/instance/methods/Destroy",
+    roblox_instance_methods_estimate_serialized_size: "roblox/instance/methods/estimateSerializedSize",
     roblox_instance_methods_find_first_ancestor: "roblox/instance/methods/FindFirstAncestor",
     roblox_instance_methods_find_first_ancestor_of_class: "roblox/instance/methods/FindFirstAncestorOfClass",
     roblox_instance_methods_find_first_ancestor_which_is_a: "roblox/instance/methods/FindFirstAncestorWhichIsA",
@@ -183,11 +190,17 @@ create_tests! {
     roblox_instance_methods_get_debug_id: "roblox/instance/methods/GetDebugId",
     roblox_instance_methods_get_descendants: "roblox/instance/methods/GetDescendants",
     roblox_instance_methods_get_full_name: "roblox/instance/methods/GetFullName",
+    roblox_instance_methods_get_descendants_of_class: "roblox/instance/methods/getDescendantsOfClass",
     roblox_instance_methods_is_a: "roblox/instance/methods/IsA",
+    roblox_instance_methods_iter_descendants: "roblox/instance/methods/iterDescendants",
     roblox_instance_methods_is_ancestor_of: "roblox/instance/methods/IsAncestorOf",
     roblox_instance_methods_is_descendant_of: "roblox/instance/methods/IsDescendantOf",
 
+    roblox_misc_approx_eq: "roblox/misc/approxEq",
+    roblox_misc_direction_vectors: "roblox/misc/directionVectors",
+    roblox_misc_resolve_layout: "roblox/misc/resolveLayout",
     roblox_misc_typeof: "roblox/misc/typeof",
+    roblox_misc_version: "roblox/misc/version",
 
     roblox_reflection_class: "roblox/reflection/class",
     roblox_reflection_database: "roblox/reflection/database",